@@ -1,9 +1,11 @@
 //! Digital I/O.
 use core::{
+    cmp::Ordering,
     convert::{From, Infallible},
-    ops::Not,
+    ops::{BitOr, BitOrAssign, Not},
+    time::Duration,
 };
-pub use embedded_hal::digital::{Error, ErrorKind, ErrorType};
+pub use embedded_hal::digital::{Error, ErrorKind, ErrorType, PinState};
 
 #[cfg(feature = "defmt-03")]
 use crate::defmt;
@@ -64,7 +66,7 @@ impl From<Polarity> for bool {
 ///
 /// This represents a typical set of output modes
 /// Implementations are free to define additional ones
-///     e.g. different drive strengths, sometimes used to shorten rise/fall time and enable faster switching and higher frequencies
+/// Drive strength is set separately, see [`DriveStrength`]
 /// TODO: Add compile-time checks for this and other similar features, since not every platform has e.g. open emitter capabilites on any or all pins
 #[derive(Debug, Clone, Copy)]
 #[cfg_attr(feature = "defmt-03", derive(defmt::Format))]
@@ -79,12 +81,46 @@ pub enum DriveMode {
     OpenSource,
 }
 
+/// GPIO Output drive strength, in milliamps.
+///
+/// Stronger drive is sometimes used to shorten rise/fall time and enable faster switching and higher frequencies
+/// Hardware typically supports a few discrete levels, see [`ConfigurableOutput::set_drive_strength`]
+/// ```rust
+/// # use embedded_hal_ext::digital::DriveStrength;
+/// let strength = DriveStrength::from_milliamps(8);
+/// assert_eq!(strength.milliamps(), 8);
+/// assert!(strength > DriveStrength::from_milliamps(2));
+/// ```
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Clone, Copy)]
+#[cfg_attr(feature = "defmt-03", derive(defmt::Format))]
+pub struct DriveStrength(u8);
+
+impl DriveStrength {
+    /// Creates a drive strength of the given number of milliamps
+    #[inline]
+    pub const fn from_milliamps(milliamps: u8) -> Self {
+        DriveStrength(milliamps)
+    }
+
+    /// Returns the drive strength in milliamps
+    #[inline]
+    pub const fn milliamps(self) -> u8 {
+        self.0
+    }
+}
+
 /// GPIO Pin bias.
 ///
 /// This represents a typical set of possible bias resistor configurations
 /// Implementations are free to define additional ones
 ///     e.g. different resistances, simultaneous pull-up and pull-down
 /// Lower resistances (stronger biasing) should have a larger absolute value than the default, and vice versa, to aid comparisons
+/// Biases are ordered by their signed value, so stronger pull-ups sort above weaker ones, and pull-downs below `Floating`
+/// ```rust
+/// # use embedded_hal_ext::digital::Bias;
+/// assert!(Bias::PullUp > Bias::Floating);
+/// assert!(Bias::Floating > Bias::PullDown);
+/// ```
 /// TODO: Add compile-time checks for this and other similar features, since not every platform has e.g. internal pulldowns that are configurable
 #[derive(Debug, PartialEq, Eq, Clone, Copy)]
 #[cfg_attr(feature = "defmt-03", derive(defmt::Format))]
@@ -100,6 +136,20 @@ pub enum Bias {
     Floating = 0,
 }
 
+impl PartialOrd for Bias {
+    #[inline]
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Bias {
+    #[inline]
+    fn cmp(&self, other: &Self) -> Ordering {
+        (*self as i8).cmp(&(*other as i8))
+    }
+}
+
 /// GPIO Pin IO mode.
 ///
 /// This represents a typical set of possible bias resistor configurations
@@ -123,7 +173,12 @@ pub enum PinMode {
     /// Platforms should aim to provide per-pin interrupt granulatity even if not directly supported in hardware
     ///     (e.g. EXTI pin mode on STM32, which often has one interrupt for multiple pins)
     Events,
-    //Analog,
+    /// Analog input mode
+    /// Pins reporting this capability implement the [`AnalogInput`] trait
+    Analog,
+    /// Platform-specific alternate function, numbered as in the platform's datasheet
+    ///     (e.g. ALT0-ALT5 on the Raspberry Pi, AF0-AF15 on STM32)
+    Alt(u8),
 }
 
 /// GPIO Pin events.
@@ -150,6 +205,303 @@ pub enum PinEvent {
     AnyEdge,
 }
 
+/// Error signalling that a pin doesn't support the requested configuration.
+///
+/// This is the canonical "not supported on this pin" error for the configuration methods of the traits in this module.
+/// Platform error types can wrap it, or use it directly as the pin's [`ErrorType::Error`].
+/// ```rust
+/// # use embedded_hal_ext::digital::{CapabilityError, Error, ErrorKind};
+/// let err = CapabilityError::UnsupportedDriveMode;
+/// assert_eq!(err.kind(), ErrorKind::Other);
+/// ```
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+#[cfg_attr(feature = "defmt-03", derive(defmt::Format))]
+#[non_exhaustive]
+pub enum CapabilityError {
+    /// The pin doesn't support the requested [`PinMode`]
+    UnsupportedMode,
+    /// The pin doesn't support the requested [`Bias`]
+    UnsupportedBias,
+    /// The pin doesn't support the requested [`DriveMode`]
+    UnsupportedDriveMode,
+    /// The requested [`DriveStrength`] is outside the range supported by the pin
+    UnsupportedDriveStrength,
+    /// The pin can't listen for the requested [`PinEvent`]s
+    UnsupportedEvent,
+    /// The pin has no configurable input hysteresis
+    UnsupportedHysteresis,
+}
+
+impl Error for CapabilityError {
+    #[inline]
+    fn kind(&self) -> ErrorKind {
+        ErrorKind::Other
+    }
+}
+
+/// Set of GPIO Pin events.
+///
+/// Used to listen for several events at once, see [`Event::listen_many`].
+/// Can be built from [`PinEvent`]s with the `|` operator.
+/// ```rust
+/// # use embedded_hal_ext::digital::{PinEvent, PinEventSet};
+/// let events = PinEvent::RisingEdge | PinEvent::FallingEdge;
+/// assert!(events.contains(PinEvent::RisingEdge));
+/// assert!(!events.contains(PinEvent::AnyEdge));
+/// ```
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Default)]
+#[cfg_attr(feature = "defmt-03", derive(defmt::Format))]
+pub struct PinEventSet(u8);
+
+impl PinEventSet {
+    /// Returns an empty set
+    #[inline]
+    pub const fn empty() -> Self {
+        PinEventSet(0)
+    }
+
+    /// Bit representing an event in the set
+    #[inline]
+    const fn bit(event: PinEvent) -> u8 {
+        1 << event as u8
+    }
+
+    /// Returns true if the set contains the event
+    #[inline]
+    pub const fn contains(self, event: PinEvent) -> bool {
+        self.0 & Self::bit(event) != 0
+    }
+
+    /// Returns true if the set contains no events
+    #[inline]
+    pub const fn is_empty(self) -> bool {
+        self.0 == 0
+    }
+
+    /// Returns the set with the event added
+    #[inline]
+    pub const fn with(self, event: PinEvent) -> Self {
+        PinEventSet(self.0 | Self::bit(event))
+    }
+
+    /// Returns the set with the event removed
+    #[inline]
+    pub const fn without(self, event: PinEvent) -> Self {
+        PinEventSet(self.0 & !Self::bit(event))
+    }
+}
+
+impl From<PinEvent> for PinEventSet {
+    #[inline]
+    fn from(event: PinEvent) -> Self {
+        PinEventSet::empty().with(event)
+    }
+}
+
+impl BitOr for PinEventSet {
+    type Output = PinEventSet;
+
+    #[inline]
+    fn bitor(self, rhs: PinEventSet) -> Self::Output {
+        PinEventSet(self.0 | rhs.0)
+    }
+}
+
+impl BitOr<PinEvent> for PinEventSet {
+    type Output = PinEventSet;
+
+    #[inline]
+    fn bitor(self, rhs: PinEvent) -> Self::Output {
+        self.with(rhs)
+    }
+}
+
+impl BitOr for PinEvent {
+    type Output = PinEventSet;
+
+    #[inline]
+    fn bitor(self, rhs: PinEvent) -> Self::Output {
+        PinEventSet::from(self).with(rhs)
+    }
+}
+
+impl BitOrAssign<PinEvent> for PinEventSet {
+    #[inline]
+    fn bitor_assign(&mut self, rhs: PinEvent) {
+        *self = self.with(rhs)
+    }
+}
+
+/// Software emulation of pin events from sampled input levels.
+///
+/// For platforms without per-pin interrupts, derives [`PinEvent`]s by comparing each sampled level with the previous one.
+/// HALs can keep one per pin and call [`sample`](SoftwareEventPoller::sample) with a fresh
+/// [`InputPin`](embedded_hal::digital::InputPin) read from their own [`Event::has_event`] implementation.
+/// Edges take priority over levels, and [`PinEvent::AnyEdge`] reports the specific edge that fired.
+/// No edge is reported for the first sample after [`listen`](SoftwareEventPoller::listen), since there's no previous level to compare with.
+/// The poller is `Copy`, so it can live in a `Cell` to be updated from `has_event(&self)`.
+/// ```rust
+/// # use embedded_hal_ext::digital::{PinEvent, PinState, SoftwareEventPoller};
+/// let mut poller = SoftwareEventPoller::new();
+/// poller.listen(PinEvent::RisingEdge.into());
+/// assert_eq!(poller.sample(PinState::Low), None);
+/// assert_eq!(poller.sample(PinState::High), Some(PinEvent::RisingEdge));
+/// assert_eq!(poller.sample(PinState::High), None);
+/// ```
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Default)]
+pub struct SoftwareEventPoller {
+    events: PinEventSet,
+    last: Option<PinState>,
+}
+
+impl SoftwareEventPoller {
+    /// Returns a poller that isn't listening for any events
+    #[inline]
+    pub const fn new() -> Self {
+        SoftwareEventPoller {
+            events: PinEventSet::empty(),
+            last: None,
+        }
+    }
+
+    /// Starts listening for the events, replacing any events already listened for
+    /// Forgets the previously sampled level
+    #[inline]
+    pub fn listen(&mut self, events: PinEventSet) {
+        self.events = events;
+        self.last = None;
+    }
+
+    /// Stops listening for events
+    #[inline]
+    pub fn stop_listening(&mut self) {
+        self.listen(PinEventSet::empty())
+    }
+
+    /// Returns true if listening for any event
+    #[inline]
+    pub const fn is_listening(&self) -> bool {
+        !self.events.is_empty()
+    }
+
+    /// Returns the events listened for
+    #[inline]
+    pub const fn events(&self) -> PinEventSet {
+        self.events
+    }
+
+    /// Records a newly sampled level, returning the listened event it triggers, if any
+    pub fn sample(&mut self, level: PinState) -> Option<PinEvent> {
+        let last = self.last.replace(level);
+        let edge = match (last, level) {
+            (Some(PinState::Low), PinState::High) => Some(PinEvent::RisingEdge),
+            (Some(PinState::High), PinState::Low) => Some(PinEvent::FallingEdge),
+            _ => None,
+        };
+        if let Some(edge) = edge {
+            if self.events.contains(edge) || self.events.contains(PinEvent::AnyEdge) {
+                return Some(edge);
+            }
+        }
+
+        let level = match level {
+            PinState::High => PinEvent::High,
+            PinState::Low => PinEvent::Low,
+        };
+        self.events.contains(level).then_some(level)
+    }
+}
+
+/// Software debouncing of an input pin, for platforms without kernel or hardware debounce.
+///
+/// Wraps an [`InputPin`](embedded_hal::digital::InputPin) and a clock returning the time elapsed since an arbitrary fixed point,
+/// like [`Event::now`]. The clock is any `Fn() -> Duration`, so the debouncer stays `no_std` and can share a platform timer.
+/// A level is only reported once every sample taken over the debounce window has read the same level.
+/// ```rust
+/// # use core::{cell::Cell, convert::Infallible, time::Duration};
+/// # use embedded_hal_ext::digital::{Debouncer, ErrorType, PinState};
+/// # struct Button;
+/// # impl ErrorType for Button { type Error = Infallible; }
+/// # impl embedded_hal::digital::InputPin for Button {
+/// #     fn is_high(&mut self) -> Result<bool, Infallible> { Ok(true) }
+/// #     fn is_low(&mut self) -> Result<bool, Infallible> { Ok(false) }
+/// # }
+/// let clock = Cell::new(Duration::ZERO);
+/// let mut button = Debouncer::new(Button, Duration::from_millis(20), || clock.get());
+/// assert_eq!(button.stable_level(), Ok(None));
+/// clock.set(Duration::from_millis(20));
+/// assert_eq!(button.stable_level(), Ok(Some(PinState::High)));
+/// assert_eq!(button.stable_level(), Ok(None));
+/// assert_eq!(button.level(), Some(PinState::High));
+/// ```
+#[derive(Debug)]
+pub struct Debouncer<P, C> {
+    pin: P,
+    clock: C,
+    window: Duration,
+    /// Last level reported as stable
+    stable: Option<PinState>,
+    /// Level read by the latest samples, and when it was first read
+    pending: Option<(PinState, Duration)>,
+}
+
+impl<P, C> Debouncer<P, C>
+where
+    P: embedded_hal::digital::InputPin,
+    C: Fn() -> Duration,
+{
+    /// Wraps the pin, reporting levels that persist for `window` according to `clock`
+    #[inline]
+    pub fn new(pin: P, window: Duration, clock: C) -> Self {
+        Debouncer {
+            pin,
+            clock,
+            window,
+            stable: None,
+            pending: None,
+        }
+    }
+
+    /// Samples the pin, returning its level if it has newly settled
+    /// Returns `Ok(None)` while the level is still bouncing or hasn't changed since it was last reported.
+    /// Must be polled more often than the window for bounces to be seen.
+    pub fn stable_level(&mut self) -> Result<Option<PinState>, P::Error> {
+        let level = PinState::from(self.pin.is_high()?);
+        let now = (self.clock)();
+        let since = match self.pending {
+            Some((pending, since)) if pending == level => since,
+            _ => {
+                self.pending = Some((level, now));
+                now
+            }
+        };
+
+        if self.stable != Some(level) && now.saturating_sub(since) >= self.window {
+            self.stable = Some(level);
+            return Ok(Some(level));
+        }
+        Ok(None)
+    }
+
+    /// Returns the last level reported by [`stable_level`](Debouncer::stable_level), if any
+    #[inline]
+    pub fn level(&self) -> Option<PinState> {
+        self.stable
+    }
+
+    /// Returns the debounce window
+    #[inline]
+    pub fn window(&self) -> Duration {
+        self.window
+    }
+
+    /// Releases the wrapped pin
+    #[inline]
+    pub fn release(self) -> P {
+        self.pin
+    }
+}
+
 /// Runtime identifier for a GPIO pin. Platform-specific, but should provide both u16 and string representations that uniquely identify the pin.
 /// A convenient `id()` implementation for e.g. STM32 or Atmel MCUs could be as follows:
 /// ```rust
@@ -165,8 +517,8 @@ pub enum PinEvent {
 ///         (((self.port as u8) as u16) << 8 | (self.pin as u16)) as u16
 ///     }
 ///
-/// #    fn name(&self) -> heapless::String<8> {
-/// #        let mut s: String<8> = String::new();
+/// #    fn name(&self) -> heapless::String<16> {
+/// #        let mut s: String<16> = String::new();
 /// #        s.push(self.port);
 /// #        s.push(char::from_digit(self.pin as u32, 10).unwrap());
 /// #        s
@@ -180,7 +532,8 @@ pub trait PinID {
     fn id(&self) -> u16;
 
     /// The standard human-readable name, similar to what's printed on a dev board silkscreen
-    fn name(&self) -> heapless::String<8>;
+    /// Up to 16 bytes long, enough for SoC pad names such as `CD0_IO0_MICCLK`
+    fn name(&self) -> heapless::String<16>;
 }
 
 /// Configurable generic GPIO pin
@@ -188,6 +541,7 @@ pub trait PinID {
 /// Capabilities can be checked at runtime as well
 pub trait Configurable: ErrorType {
     /// Returns a list of the pin's supported modes
+    /// Should include [`PinMode::Analog`] if and only if the pin implements [`AnalogInput`]
     fn capabilities(self: &Self) -> &[PinMode];
 
     /// Returns the GPIO Pin ID, usually a number, but platforms differ in their canonical representations.
@@ -201,8 +555,26 @@ pub trait Configurable: ErrorType {
     fn set_polarity(self: &mut Self, polarity: Polarity) -> Result<Polarity, Self::Error>;
 
     /// Sets the bias of the pin, enabling or disabling internal pull-up or pull-down resistors
+    /// Platform implementation should return [`CapabilityError::UnsupportedBias`] if the bias is unsupported on the pin.
     /// TODO: Add compile-time checks for this and other similar features, since not every platform has e.g. internal pulldowns that are configurable
     fn set_bias(self: &mut Self, direction: Bias) -> Result<Bias, Self::Error>;
+
+    /// Returns true if the pin supports the mode, i.e. it's listed in [`capabilities`](Configurable::capabilities)
+    fn supports(&self, mode: PinMode) -> bool {
+        self.capabilities().contains(&mode)
+    }
+
+    /// Returns [`CapabilityError::UnsupportedMode`] if the pin doesn't support the mode
+    /// Intended for the top of platform `into_input`/`into_output` implementations, so callers don't need to check capabilities themselves.
+    fn ensure_supports(&self, mode: PinMode) -> Result<(), Self::Error>
+    where
+        Self::Error: From<CapabilityError>,
+    {
+        match self.supports(mode) {
+            true => Ok(()),
+            false => Err(CapabilityError::UnsupportedMode.into()),
+        }
+    }
 }
 
 impl<T: Configurable + ?Sized> Configurable for &mut T {
@@ -235,7 +607,25 @@ impl<T: Configurable + ?Sized> Configurable for &mut T {
 /// GPIO pin that can be configured as an input
 pub trait ConfigurableInput: Configurable + embedded_hal::digital::InputPin {
     /// Converts pin into input mode
+    /// Platform implementation should return [`CapabilityError::UnsupportedMode`] if the pin lacks the mode, see [`Configurable::ensure_supports`].
     fn into_input(self: &mut Self) -> Result<(), Self::Error>;
+
+    /// Returns true if the pin's input hysteresis (Schmitt trigger) can be configured
+    /// Defaults to false, for pins without configurable hysteresis.
+    fn supports_input_hysteresis(&self) -> bool {
+        false
+    }
+
+    /// Enables or disables input hysteresis (Schmitt trigger), which makes noisy inputs less prone to spurious transitions
+    /// Returns the state actually applied
+    /// Defaults to returning [`CapabilityError::UnsupportedHysteresis`], platforms with configurable hysteresis should override it
+    /// along with [`supports_input_hysteresis`](ConfigurableInput::supports_input_hysteresis).
+    fn set_input_hysteresis(self: &mut Self, _enabled: bool) -> Result<bool, Self::Error>
+    where
+        Self::Error: From<CapabilityError>,
+    {
+        Err(CapabilityError::UnsupportedHysteresis.into())
+    }
 }
 
 impl<T: ConfigurableInput + ?Sized> ConfigurableInput for &mut T {
@@ -243,6 +633,41 @@ impl<T: ConfigurableInput + ?Sized> ConfigurableInput for &mut T {
     fn into_input(self: &mut Self) -> Result<(), Self::Error> {
         T::into_input(self)
     }
+
+    #[inline]
+    fn supports_input_hysteresis(&self) -> bool {
+        T::supports_input_hysteresis(self)
+    }
+
+    #[inline]
+    fn set_input_hysteresis(self: &mut Self, enabled: bool) -> Result<bool, Self::Error>
+    where
+        Self::Error: From<CapabilityError>,
+    {
+        T::set_input_hysteresis(self, enabled)
+    }
+}
+
+/// GPIO pin that can be read by an analog-to-digital converter
+/// Only implemented for ADC-capable pins, digital-only pins don't need to implement this trait
+pub trait AnalogInput: Configurable {
+    /// Reads the raw converted value, ranging from zero up to `2^resolution_bits() - 1`
+    fn read_raw(self: &mut Self) -> Result<u16, Self::Error>;
+
+    /// Returns the resolution of the converter in bits
+    fn resolution_bits(&self) -> u8;
+}
+
+impl<T: AnalogInput + ?Sized> AnalogInput for &mut T {
+    #[inline]
+    fn read_raw(self: &mut Self) -> Result<u16, Self::Error> {
+        T::read_raw(self)
+    }
+
+    #[inline]
+    fn resolution_bits(&self) -> u8 {
+        T::resolution_bits(self)
+    }
 }
 
 /// GPIO Pin can be listened to for events in a non-blocking manner
@@ -250,7 +675,7 @@ impl<T: ConfigurableInput + ?Sized> ConfigurableInput for &mut T {
 pub trait Event: ConfigurableInput {
     /// Listen for events
     /// Default options. Platform should provide a sane (i.e. power-efficient, reponsive) default that this calls.
-    /// Enforces no simultaneous event listening
+    /// Enforces no simultaneous event listening, see [`listen_many`](Event::listen_many) for that
     fn listen(self: &mut Self, event: PinEvent) {
         self.stop_listening();
         self.listen_for(event)
@@ -261,6 +686,17 @@ pub trait Event: ConfigurableInput {
     /// Platforms should generally only allow listening for one event at a time on a pin via trait functions.
     fn listen_for(self: &mut Self, event: PinEvent);
 
+    /// Listen for several events simultaneously, replacing any events already listened for
+    /// e.g. rising and falling edges armed separately, rather than collapsed to `AnyEdge`
+    /// Platforms without hardware support may emulate this in software, or return [`CapabilityError::UnsupportedEvent`] for sets they can't listen for.
+    /// Defaults to returning [`CapabilityError::UnsupportedEvent`] for any set, platforms that can listen for several events should override it.
+    fn listen_many(self: &mut Self, _events: PinEventSet) -> Result<(), Self::Error>
+    where
+        Self::Error: From<CapabilityError>,
+    {
+        Err(CapabilityError::UnsupportedEvent.into())
+    }
+
     /// Stop listening for events. Clears interrupt status flag or similar.
     fn stop_listening(self: &mut Self);
 
@@ -268,11 +704,34 @@ pub trait Event: ConfigurableInput {
     fn is_listening(&self) -> bool;
 
     /// Polls if the listened event has occurred. Clears interrupt status flag or similar if it has.
+    /// When listening for several events, returns the specific event that fired.
     fn has_event(&self) -> Option<PinEvent>;
 
     /// Gets the latest event. Returns `nb::Error::WouldBlock``
     fn get_event(&mut self) -> nb::Result<PinEvent, Infallible>;
 
+    /// Returns the time elapsed since an arbitrary fixed point, from a monotonic platform clock
+    /// Used to bound blocking waits, see [`wait_for_timeout`](Event::wait_for_timeout)
+    fn now(&self) -> Duration;
+
+    /// Blocks until the event occurs or the timeout elapses, polling [`has_event`](Event::has_event)
+    /// Returns `Ok(None)` on timeout. Stops listening for events before returning.
+    /// Platforms may override this with a more power-efficient implementation
+    fn wait_for_timeout(self: &mut Self, event: PinEvent, timeout: Duration) -> Result<Option<PinEvent>, Self::Error> {
+        self.listen(event);
+        let start = self.now();
+        let fired = loop {
+            if let Some(fired) = self.has_event() {
+                break Some(fired);
+            }
+            if self.now().saturating_sub(start) >= timeout {
+                break None;
+            }
+        };
+        self.stop_listening();
+        Ok(fired)
+    }
+
     #[cfg(any(feature = "async", doc))]
     // Base function for `embedded-hal-async` "Wait" trait implementation
     async fn wait_for(self: &mut Self, event: PinEvent) -> Result<PinEvent, Self::Error>;
@@ -284,6 +743,14 @@ impl<T: Event + ?Sized> Event for &mut T {
         T::listen_for(self, event)
     }
 
+    #[inline]
+    fn listen_many(self: &mut Self, events: PinEventSet) -> Result<(), Self::Error>
+    where
+        Self::Error: From<CapabilityError>,
+    {
+        T::listen_many(self, events)
+    }
+
     #[inline]
     fn stop_listening(self: &mut Self) {
         T::stop_listening(self)
@@ -304,6 +771,16 @@ impl<T: Event + ?Sized> Event for &mut T {
         T::get_event(self)
     }
 
+    #[inline]
+    fn now(&self) -> Duration {
+        T::now(self)
+    }
+
+    #[inline]
+    fn wait_for_timeout(self: &mut Self, event: PinEvent, timeout: Duration) -> Result<Option<PinEvent>, Self::Error> {
+        T::wait_for_timeout(self, event, timeout)
+    }
+
     #[cfg(feature = "async")]
     #[inline]
     async fn wait_for(self: &mut Self, event: PinEvent) -> Result<PinEvent, Self::Error> {
@@ -311,17 +788,79 @@ impl<T: Event + ?Sized> Event for &mut T {
     }
 }
 
+/// GPIO Pin can count edges, e.g. for rotary encoders and tachometers
+/// Counting may be done in hardware, or in software by incrementing the count in the platform's event handling
+pub trait PulseCounter: Event {
+    /// Starts counting occurrences of the event, usually `RisingEdge`, `FallingEdge` or `AnyEdge`
+    /// Replaces any event already counted, without resetting the count
+    fn start_counting(self: &mut Self, edge: PinEvent);
+
+    /// Returns the number of events counted since counting started or the count was last reset
+    /// Wraps around on overflow
+    fn count(&self) -> u32;
+
+    /// Resets the count to zero
+    fn reset_count(self: &mut Self);
+}
+
+impl<T: PulseCounter + ?Sized> PulseCounter for &mut T {
+    #[inline]
+    fn start_counting(self: &mut Self, edge: PinEvent) {
+        T::start_counting(self, edge)
+    }
+
+    #[inline]
+    fn count(&self) -> u32 {
+        T::count(self)
+    }
+
+    #[inline]
+    fn reset_count(self: &mut Self) {
+        T::reset_count(self)
+    }
+}
+
 /// GPIO Pin can be configured as an output
 pub trait ConfigurableOutput: Configurable + embedded_hal::digital::OutputPin {
     /// Converts pin into output mode
+    /// Platform implementation should return [`CapabilityError::UnsupportedMode`] if the pin lacks the mode, see [`Configurable::ensure_supports`].
     fn into_output(self: &mut Self) -> Result<(), Self::Error>;
 
     /// Sets drive mode of pin
     /// Should also set pin polarity to Inverted
-    /// Platform implementation should return an error if a specific mode is unsupported on a specific pin.
+    /// Platform implementation should return [`CapabilityError::UnsupportedDriveMode`] if a specific mode is unsupported on a specific pin.
     /// Cross-platform crates should perform runtime checks currently
     /// TODO: Add compile-time checks for this and other similar features
     fn set_drive_mode(self: &mut Self, mode: DriveMode) -> Result<DriveMode, Self::Error>;
+
+    /// Sets drive strength of pin
+    /// Returns the strength actually applied, since hardware typically quantizes it to a few supported levels
+    /// Platform implementation should apply the nearest supported level, and return [`CapabilityError::UnsupportedDriveStrength`] if the requested strength is outside the supported range instead of clamping it.
+    fn set_drive_strength(self: &mut Self, strength: DriveStrength) -> Result<DriveStrength, Self::Error>;
+
+    /// Inverts the output level of the pin, returning the new level
+    /// Reads the current level through [`StatefulOutputPin`](embedded_hal::digital::StatefulOutputPin), so pins that can't read back their output should track the last written level in that implementation.
+    /// Call as `ConfigurableOutput::toggle(&mut pin)` if `StatefulOutputPin` is also in scope, since it has a `toggle` method of its own.
+    fn toggle(self: &mut Self) -> Result<PinState, Self::Error>
+    where
+        Self: embedded_hal::digital::StatefulOutputPin,
+    {
+        let state = match self.is_set_high()? {
+            true => PinState::Low,
+            false => PinState::High,
+        };
+        self.set_state(state)?;
+        Ok(state)
+    }
+
+    /// Configures the pin as an open-drain output with the given bias in one call, as needed for I2C-style buses
+    /// `None` leaves the line floating, relying on an external pull-up. Returns the bias applied.
+    /// The default implementation sets the drive mode, then the bias. Platforms that can apply both in a single
+    /// register write or ioctl should override this, so the line never sits in a partially applied configuration.
+    fn set_open_drain(self: &mut Self, pull: Option<Bias>) -> Result<Bias, Self::Error> {
+        self.set_drive_mode(DriveMode::OpenDrain)?;
+        self.set_bias(pull.unwrap_or(Bias::Floating))
+    }
 }
 
 impl<T: ConfigurableOutput + ?Sized> ConfigurableOutput for &mut T {
@@ -334,7 +873,764 @@ impl<T: ConfigurableOutput + ?Sized> ConfigurableOutput for &mut T {
     fn set_drive_mode(self: &mut Self, mode: DriveMode) -> Result<DriveMode, Self::Error> {
         T::set_drive_mode(self, mode)
     }
+
+    #[inline]
+    fn set_drive_strength(self: &mut Self, strength: DriveStrength) -> Result<DriveStrength, Self::Error> {
+        T::set_drive_strength(self, strength)
+    }
+
+    #[inline]
+    fn set_open_drain(self: &mut Self, pull: Option<Bias>) -> Result<Bias, Self::Error> {
+        T::set_open_drain(self, pull)
+    }
 }
 
 /// Configurable GPIO Pin that implements both Input and Output traits
 pub trait ConfigurableIO: ConfigurableInput + ConfigurableOutput {}
+
+/// GPIO port, a group of up to 32 pins read and written together
+/// Bit `n` of a value corresponds to the pin at index `n` of the port, e.g. a whole GPIO controller register
+/// Reads and writes should be atomic with respect to the other pins of the port, avoiding the races of reading or writing pins one at a time
+/// Independent of the per-pin traits, so platforms can implement both for the same hardware
+pub trait Port {
+    /// Reads the levels of all pins of the port, with bits beyond `width()` cleared
+    fn read_all(&self) -> u32;
+
+    /// Writes `values` to the pins selected by `mask`, leaving the other pins untouched
+    fn write_all(self: &mut Self, values: u32, mask: u32);
+
+    /// Returns the number of pins in the port
+    fn width(&self) -> u8;
+}
+
+impl<T: Port + ?Sized> Port for &mut T {
+    #[inline]
+    fn read_all(&self) -> u32 {
+        T::read_all(self)
+    }
+
+    #[inline]
+    fn write_all(self: &mut Self, values: u32, mask: u32) {
+        T::write_all(self, values, mask)
+    }
+
+    #[inline]
+    fn width(&self) -> u8 {
+        T::width(self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use core::cell::Cell;
+
+    /// Push-pull and open drain output pin supporting drive strengths of 2 to 16 mA in steps of 2 mA
+    struct MockOutputPin {
+        modes: &'static [PinMode],
+        strength: DriveStrength,
+        drive_mode: DriveMode,
+        bias: Bias,
+        high: bool,
+    }
+
+    impl MockOutputPin {
+        fn new() -> Self {
+            MockOutputPin {
+                modes: &[PinMode::Output],
+                strength: DriveStrength::from_milliamps(8),
+                drive_mode: DriveMode::PushPull,
+                bias: Bias::Floating,
+                high: false,
+            }
+        }
+    }
+
+    impl ErrorType for MockOutputPin {
+        type Error = CapabilityError;
+    }
+
+    impl Configurable for MockOutputPin {
+        fn capabilities(&self) -> &[PinMode] {
+            self.modes
+        }
+
+        fn pin(&self) -> impl PinID {
+            MockPinID(1)
+        }
+
+        fn mode(&self) -> PinMode {
+            PinMode::Output
+        }
+
+        fn set_polarity(&mut self, polarity: Polarity) -> Result<Polarity, Self::Error> {
+            Ok(polarity)
+        }
+
+        fn set_bias(&mut self, direction: Bias) -> Result<Bias, Self::Error> {
+            self.bias = direction;
+            Ok(direction)
+        }
+    }
+
+    impl embedded_hal::digital::OutputPin for MockOutputPin {
+        fn set_low(&mut self) -> Result<(), Self::Error> {
+            self.high = false;
+            Ok(())
+        }
+
+        fn set_high(&mut self) -> Result<(), Self::Error> {
+            self.high = true;
+            Ok(())
+        }
+    }
+
+    impl embedded_hal::digital::StatefulOutputPin for MockOutputPin {
+        fn is_set_high(&mut self) -> Result<bool, Self::Error> {
+            Ok(self.high)
+        }
+
+        fn is_set_low(&mut self) -> Result<bool, Self::Error> {
+            Ok(!self.high)
+        }
+    }
+
+    impl ConfigurableOutput for MockOutputPin {
+        fn into_output(&mut self) -> Result<(), Self::Error> {
+            self.ensure_supports(PinMode::Output)
+        }
+
+        fn set_drive_mode(&mut self, mode: DriveMode) -> Result<DriveMode, Self::Error> {
+            match mode {
+                DriveMode::PushPull | DriveMode::OpenDrain => {
+                    self.drive_mode = mode;
+                    Ok(mode)
+                }
+                _ => Err(CapabilityError::UnsupportedDriveMode),
+            }
+        }
+
+        fn set_drive_strength(&mut self, strength: DriveStrength) -> Result<DriveStrength, Self::Error> {
+            let milliamps = strength.milliamps();
+            if !(1..=17).contains(&milliamps) {
+                return Err(CapabilityError::UnsupportedDriveStrength);
+            }
+            // Round to the nearest even level, rounding halfway values up
+            let level = milliamps.div_ceil(2).clamp(1, 8);
+            self.strength = DriveStrength::from_milliamps(level * 2);
+            Ok(self.strength)
+        }
+    }
+
+    #[test]
+    fn test_ensure_supports() {
+        let mut pin = MockOutputPin::new();
+        assert!(pin.supports(PinMode::Output));
+        assert!(!pin.supports(PinMode::Input));
+        assert_eq!(pin.into_output(), Ok(()));
+
+        let mut pin = MockOutputPin {
+            modes: &[PinMode::Input],
+            ..MockOutputPin::new()
+        };
+        assert_eq!(pin.into_output(), Err(CapabilityError::UnsupportedMode));
+        assert_eq!(pin.ensure_supports(PinMode::Input), Ok(()));
+
+        let mut pin = MockAdcPin { raw: 0 };
+        assert!(pin.supports(PinMode::Analog));
+        assert_eq!(pin.into_input(), Ok(()));
+        assert_eq!(
+            pin.ensure_supports(PinMode::Events),
+            Err(CapabilityError::UnsupportedMode)
+        );
+    }
+
+    #[test]
+    fn test_open_drain() {
+        let mut pin = MockOutputPin::new();
+        assert_eq!(pin.set_open_drain(Some(Bias::PullUp)), Ok(Bias::PullUp));
+        assert!(matches!(pin.drive_mode, DriveMode::OpenDrain));
+        assert_eq!(pin.bias, Bias::PullUp);
+
+        // No pull leaves the line floating for an external pull-up
+        let mut pin = &mut pin;
+        assert_eq!(ConfigurableOutput::set_open_drain(&mut pin, None), Ok(Bias::Floating));
+        assert!(matches!(pin.drive_mode, DriveMode::OpenDrain));
+        assert_eq!(pin.bias, Bias::Floating);
+    }
+
+    #[test]
+    fn test_drive_strength() {
+        let mut pin = MockOutputPin::new();
+        let mut set = |ma| {
+            pin.set_drive_strength(DriveStrength::from_milliamps(ma))
+                .map(DriveStrength::milliamps)
+        };
+
+        assert_eq!(set(8), Ok(8));
+        assert_eq!(set(5), Ok(6));
+        assert_eq!(set(1), Ok(2));
+        assert_eq!(set(17), Ok(16));
+        assert_eq!(set(0), Err(CapabilityError::UnsupportedDriveStrength));
+        assert_eq!(set(24), Err(CapabilityError::UnsupportedDriveStrength));
+        assert_eq!(pin.strength, DriveStrength::from_milliamps(16));
+    }
+
+    #[test]
+    fn test_toggle() {
+        let mut pin = MockOutputPin::new();
+        assert_eq!(ConfigurableOutput::toggle(&mut pin), Ok(PinState::High));
+        assert!(pin.high);
+        assert_eq!(ConfigurableOutput::toggle(&mut pin), Ok(PinState::Low));
+        assert!(!pin.high);
+
+        // Through a mutable reference
+        let mut pin = &mut pin;
+        assert_eq!(ConfigurableOutput::toggle(&mut pin), Ok(PinState::High));
+    }
+
+    #[test]
+    fn test_unsupported_drive_mode() {
+        let mut pin = MockOutputPin::new();
+        assert!(matches!(pin.set_drive_mode(DriveMode::OpenDrain), Ok(DriveMode::OpenDrain)));
+
+        let err = pin.set_drive_mode(DriveMode::OpenSource).unwrap_err();
+        assert_eq!(err, CapabilityError::UnsupportedDriveMode);
+        assert_eq!(err.kind(), ErrorKind::Other);
+    }
+
+    /// Input pin with separate rising and falling edge triggers
+    /// Its clock advances by a millisecond every time it is read
+    struct MockEventPin {
+        armed: PinEventSet,
+        fired: Cell<Option<PinEvent>>,
+        clock: Cell<Duration>,
+        /// Edge the pin will see once the clock reaches the given time
+        scheduled: Option<(Duration, PinEvent)>,
+        hysteresis: bool,
+        counting: Option<PinEvent>,
+        pulses: Cell<u32>,
+    }
+
+    impl MockEventPin {
+        fn new() -> Self {
+            MockEventPin {
+                armed: PinEventSet::empty(),
+                fired: Cell::new(None),
+                clock: Cell::new(Duration::ZERO),
+                scheduled: None,
+                hysteresis: false,
+                counting: None,
+                pulses: Cell::new(0),
+            }
+        }
+
+        /// Simulates the hardware detecting an edge
+        fn trigger(&self, edge: PinEvent) {
+            if self.armed.contains(edge) {
+                self.fired.set(Some(edge));
+            }
+            if self.counting == Some(edge) {
+                self.pulses.set(self.pulses.get().wrapping_add(1));
+            }
+        }
+    }
+
+    impl ErrorType for MockEventPin {
+        type Error = CapabilityError;
+    }
+
+    impl Configurable for MockEventPin {
+        fn capabilities(&self) -> &[PinMode] {
+            &[PinMode::Input, PinMode::Events]
+        }
+
+        fn pin(&self) -> impl PinID {
+            MockPinID(2)
+        }
+
+        fn mode(&self) -> PinMode {
+            PinMode::Events
+        }
+
+        fn set_polarity(&mut self, polarity: Polarity) -> Result<Polarity, Self::Error> {
+            Ok(polarity)
+        }
+
+        fn set_bias(&mut self, direction: Bias) -> Result<Bias, Self::Error> {
+            Ok(direction)
+        }
+    }
+
+    impl embedded_hal::digital::InputPin for MockEventPin {
+        fn is_high(&mut self) -> Result<bool, Self::Error> {
+            Ok(false)
+        }
+
+        fn is_low(&mut self) -> Result<bool, Self::Error> {
+            Ok(true)
+        }
+    }
+
+    impl ConfigurableInput for MockEventPin {
+        fn into_input(&mut self) -> Result<(), Self::Error> {
+            self.ensure_supports(PinMode::Input)
+        }
+
+        fn supports_input_hysteresis(&self) -> bool {
+            true
+        }
+
+        fn set_input_hysteresis(&mut self, enabled: bool) -> Result<bool, Self::Error> {
+            self.hysteresis = enabled;
+            Ok(enabled)
+        }
+    }
+
+    impl Event for MockEventPin {
+        fn listen_for(&mut self, event: PinEvent) {
+            self.armed = event.into();
+        }
+
+        fn listen_many(&mut self, events: PinEventSet) -> Result<(), Self::Error> {
+            // Only edge triggers are available in hardware
+            let other = events.without(PinEvent::RisingEdge).without(PinEvent::FallingEdge);
+            if !other.is_empty() {
+                return Err(CapabilityError::UnsupportedEvent);
+            }
+            self.armed = events;
+            Ok(())
+        }
+
+        fn stop_listening(&mut self) {
+            self.armed = PinEventSet::empty();
+            self.fired.set(None);
+        }
+
+        fn is_listening(&self) -> bool {
+            !self.armed.is_empty()
+        }
+
+        fn has_event(&self) -> Option<PinEvent> {
+            self.fired.take()
+        }
+
+        fn get_event(&mut self) -> nb::Result<PinEvent, Infallible> {
+            self.has_event().ok_or(nb::Error::WouldBlock)
+        }
+
+        fn now(&self) -> Duration {
+            let now = self.clock.get() + Duration::from_millis(1);
+            self.clock.set(now);
+            if let Some((at, edge)) = self.scheduled {
+                if now >= at {
+                    self.trigger(edge);
+                }
+            }
+            now
+        }
+
+        #[cfg(feature = "async")]
+        async fn wait_for(&mut self, event: PinEvent) -> Result<PinEvent, Self::Error> {
+            Ok(event)
+        }
+    }
+
+    #[test]
+    fn test_listen_many() {
+        let mut pin = MockEventPin::new();
+        pin.listen_many(PinEvent::RisingEdge | PinEvent::FallingEdge).unwrap();
+        assert!(pin.is_listening());
+        assert_eq!(pin.has_event(), None);
+
+        pin.trigger(PinEvent::RisingEdge);
+        assert_eq!(pin.has_event(), Some(PinEvent::RisingEdge));
+        assert_eq!(pin.has_event(), None);
+
+        pin.trigger(PinEvent::FallingEdge);
+        assert_eq!(pin.get_event(), Ok(PinEvent::FallingEdge));
+        assert_eq!(pin.get_event(), Err(nb::Error::WouldBlock));
+
+        // Only the armed edge fires
+        pin.listen(PinEvent::FallingEdge);
+        pin.trigger(PinEvent::RisingEdge);
+        assert_eq!(pin.has_event(), None);
+
+        assert_eq!(
+            pin.listen_many(PinEvent::High | PinEvent::RisingEdge),
+            Err(CapabilityError::UnsupportedEvent)
+        );
+    }
+
+    impl PulseCounter for MockEventPin {
+        fn start_counting(&mut self, edge: PinEvent) {
+            self.counting = Some(edge);
+        }
+
+        fn count(&self) -> u32 {
+            self.pulses.get()
+        }
+
+        fn reset_count(&mut self) {
+            self.pulses.set(0);
+        }
+    }
+
+    #[test]
+    fn test_pulse_counter() {
+        let mut pin = MockEventPin::new();
+        pin.trigger(PinEvent::RisingEdge);
+        assert_eq!(pin.count(), 0);
+
+        pin.start_counting(PinEvent::RisingEdge);
+        for _ in 0..5 {
+            pin.trigger(PinEvent::RisingEdge);
+            pin.trigger(PinEvent::FallingEdge);
+        }
+        assert_eq!(pin.count(), 5);
+
+        pin.reset_count();
+        assert_eq!(pin.count(), 0);
+        pin.trigger(PinEvent::RisingEdge);
+        assert_eq!(pin.count(), 1);
+    }
+
+    #[test]
+    fn test_wait_for_timeout() {
+        let mut pin = MockEventPin::new();
+        pin.scheduled = Some((Duration::from_millis(5), PinEvent::RisingEdge));
+        let fired = pin.wait_for_timeout(PinEvent::RisingEdge, Duration::from_millis(10));
+        assert_eq!(fired, Ok(Some(PinEvent::RisingEdge)));
+        assert!(pin.clock.get() < Duration::from_millis(10));
+        assert!(!pin.is_listening());
+
+        // The edge arrives after the deadline
+        let mut pin = MockEventPin::new();
+        pin.scheduled = Some((Duration::from_millis(50), PinEvent::RisingEdge));
+        let fired = pin.wait_for_timeout(PinEvent::RisingEdge, Duration::from_millis(10));
+        assert_eq!(fired, Ok(None));
+        assert!(pin.clock.get() >= Duration::from_millis(10));
+        assert!(!pin.is_listening());
+
+        // Edges not listened for don't end the wait
+        let mut pin = MockEventPin::new();
+        pin.scheduled = Some((Duration::from_millis(5), PinEvent::FallingEdge));
+        let fired = pin.wait_for_timeout(PinEvent::RisingEdge, Duration::from_millis(10));
+        assert_eq!(fired, Ok(None));
+    }
+
+    #[test]
+    fn test_pin_event_set() {
+        let mut events = PinEventSet::empty();
+        assert!(events.is_empty());
+        events |= PinEvent::High;
+        assert_eq!(events, PinEventSet::from(PinEvent::High));
+        let events = events | PinEvent::Low;
+        assert!(events.contains(PinEvent::High) && events.contains(PinEvent::Low));
+        assert!(!events.contains(PinEvent::AnyEdge));
+        assert_eq!(events.without(PinEvent::High), PinEvent::Low.into());
+    }
+
+    /// 8-pin port backed by a register
+    struct MockPort {
+        register: u32,
+    }
+
+    impl Port for MockPort {
+        fn read_all(&self) -> u32 {
+            self.register & 0xFF
+        }
+
+        fn write_all(&mut self, values: u32, mask: u32) {
+            let mask = mask & 0xFF;
+            self.register = (self.register & !mask) | (values & mask);
+        }
+
+        fn width(&self) -> u8 {
+            8
+        }
+    }
+
+    #[test]
+    fn test_port() {
+        let mut port = MockPort { register: 0b1010_0101 };
+        assert_eq!(port.width(), 8);
+
+        port.write_all(0b0000_1111, 0b0011_1100);
+        assert_eq!(port.read_all(), 0b1000_1101);
+
+        // Bits beyond the port width are ignored
+        port.write_all(u32::MAX, 0xFFFF_FF00);
+        assert_eq!(port.read_all(), 0b1000_1101);
+
+        let mut port = &mut port;
+        Port::write_all(&mut port, 0, u32::MAX);
+        assert_eq!(Port::read_all(&port), 0);
+    }
+
+    #[test]
+    fn test_long_pin_name() {
+        assert_eq!(MockPinID(0).name(), "ADC0");
+        assert_eq!(MockPinID(1).name(), "CD0_IO0_MICCLK");
+        let pin = MockOutputPin::new();
+        assert_eq!(pin.pin().name(), "CD0_IO0_MICCLK");
+    }
+
+    #[test]
+    fn test_bias_ordering() {
+        assert!(Bias::PullUp > Bias::Floating);
+        assert!(Bias::Floating > Bias::PullDown);
+        assert!(Bias::PullUp > Bias::PullDown);
+
+        let biases = [Bias::PullUp, Bias::PullDown, Bias::Floating];
+        for a in biases {
+            for b in biases {
+                assert_eq!(a.cmp(&b), (a as i8).cmp(&(b as i8)));
+            }
+        }
+
+        let mut sorted = biases;
+        sorted.sort();
+        assert_eq!(sorted, [Bias::PullDown, Bias::Floating, Bias::PullUp]);
+    }
+
+    /// Pin that reads a fixed value from a 12-bit converter
+    struct MockAdcPin {
+        raw: u16,
+    }
+
+    struct MockPinID(u16);
+
+    impl PinID for MockPinID {
+        fn id(&self) -> u16 {
+            self.0
+        }
+
+        fn name(&self) -> heapless::String<16> {
+            match self.0 {
+                0 => heapless::String::try_from("ADC0").unwrap(),
+                _ => heapless::String::try_from("CD0_IO0_MICCLK").unwrap(),
+            }
+        }
+    }
+
+    impl ErrorType for MockAdcPin {
+        type Error = CapabilityError;
+    }
+
+    impl Configurable for MockAdcPin {
+        fn capabilities(&self) -> &[PinMode] {
+            &[PinMode::Input, PinMode::Analog]
+        }
+
+        fn pin(&self) -> impl PinID {
+            MockPinID(0)
+        }
+
+        fn mode(&self) -> PinMode {
+            PinMode::Analog
+        }
+
+        fn set_polarity(&mut self, polarity: Polarity) -> Result<Polarity, Self::Error> {
+            Ok(polarity)
+        }
+
+        fn set_bias(&mut self, direction: Bias) -> Result<Bias, Self::Error> {
+            Ok(direction)
+        }
+    }
+
+    impl embedded_hal::digital::InputPin for MockAdcPin {
+        fn is_high(&mut self) -> Result<bool, Self::Error> {
+            Ok(self.raw >= 1 << 11)
+        }
+
+        fn is_low(&mut self) -> Result<bool, Self::Error> {
+            Ok(self.raw < 1 << 11)
+        }
+    }
+
+    impl ConfigurableInput for MockAdcPin {
+        fn into_input(&mut self) -> Result<(), Self::Error> {
+            self.ensure_supports(PinMode::Input)
+        }
+    }
+
+    #[test]
+    fn test_input_hysteresis() {
+        let mut pin = MockEventPin::new();
+        assert!(pin.supports_input_hysteresis());
+        assert_eq!(pin.set_input_hysteresis(true), Ok(true));
+        assert!(pin.hysteresis);
+        assert_eq!(pin.set_input_hysteresis(false), Ok(false));
+        assert!(!pin.hysteresis);
+
+        // Pins without hysteresis get the default implementations
+        let mut pin = MockAdcPin { raw: 0 };
+        assert!(!pin.supports_input_hysteresis());
+        assert_eq!(
+            pin.set_input_hysteresis(true),
+            Err(CapabilityError::UnsupportedHysteresis)
+        );
+        let mut pin = &mut pin;
+        assert_eq!(
+            ConfigurableInput::set_input_hysteresis(&mut pin, false),
+            Err(CapabilityError::UnsupportedHysteresis)
+        );
+    }
+
+    /// Only polled by level, so it can't listen for events at all
+    impl Event for MockAdcPin {
+        fn listen_for(&mut self, _event: PinEvent) {}
+
+        fn stop_listening(&mut self) {}
+
+        fn is_listening(&self) -> bool {
+            false
+        }
+
+        fn has_event(&self) -> Option<PinEvent> {
+            None
+        }
+
+        fn get_event(&mut self) -> nb::Result<PinEvent, Infallible> {
+            Err(nb::Error::WouldBlock)
+        }
+
+        fn now(&self) -> Duration {
+            Duration::ZERO
+        }
+
+        #[cfg(feature = "async")]
+        async fn wait_for(&mut self, event: PinEvent) -> Result<PinEvent, Self::Error> {
+            Ok(event)
+        }
+    }
+
+    #[test]
+    fn test_listen_many_unsupported() {
+        // Pins without multi-event support get the default implementation
+        let mut pin = MockAdcPin { raw: 0 };
+        assert_eq!(
+            pin.listen_many(PinEvent::RisingEdge | PinEvent::FallingEdge),
+            Err(CapabilityError::UnsupportedEvent)
+        );
+        let mut pin = &mut pin;
+        assert_eq!(
+            Event::listen_many(&mut pin, PinEvent::High.into()),
+            Err(CapabilityError::UnsupportedEvent)
+        );
+        assert!(!pin.is_listening());
+    }
+
+    impl AnalogInput for MockAdcPin {
+        fn read_raw(&mut self) -> Result<u16, Self::Error> {
+            Ok(self.raw)
+        }
+
+        fn resolution_bits(&self) -> u8 {
+            12
+        }
+    }
+
+    #[test]
+    fn test_analog_input() {
+        let mut pin = MockAdcPin { raw: 0x0ABC };
+        assert!(pin.capabilities().contains(&PinMode::Analog));
+        assert_eq!(pin.read_raw(), Ok(0x0ABC));
+        assert_eq!(pin.resolution_bits(), 12);
+
+        // Through a mutable reference
+        let mut pin = &mut pin;
+        assert_eq!(AnalogInput::read_raw(&mut pin), Ok(0x0ABC));
+        assert!(pin.read_raw().unwrap() < 1 << pin.resolution_bits());
+    }
+
+    #[test]
+    fn test_software_event_poller() {
+        use PinState::{High, Low};
+
+        let mut poller = SoftwareEventPoller::new();
+        assert!(!poller.is_listening());
+        assert_eq!(poller.sample(High), None);
+
+        poller.listen(PinEvent::AnyEdge | PinEvent::High);
+        assert_eq!(poller.sample(Low), None);
+        assert_eq!(poller.sample(Low), None);
+        assert_eq!(poller.sample(High), Some(PinEvent::RisingEdge));
+        assert_eq!(poller.sample(High), Some(PinEvent::High));
+        assert_eq!(poller.sample(Low), Some(PinEvent::FallingEdge));
+        assert_eq!(poller.sample(High), Some(PinEvent::RisingEdge));
+
+        // Only the listened edge fires, and listening again forgets the last level
+        poller.listen(PinEvent::FallingEdge | PinEvent::Low);
+        assert_eq!(poller.sample(Low), Some(PinEvent::Low));
+        assert_eq!(poller.sample(High), None);
+        assert_eq!(poller.sample(Low), Some(PinEvent::FallingEdge));
+        assert_eq!(poller.sample(Low), Some(PinEvent::Low));
+
+        poller.stop_listening();
+        assert_eq!(poller.events(), PinEventSet::empty());
+        assert_eq!(poller.sample(High), None);
+    }
+
+    /// Input pin reading a shared level, so tests can bounce it while it's owned by a [`Debouncer`]
+    struct MockBouncyPin<'a> {
+        level: &'a Cell<bool>,
+    }
+
+    impl ErrorType for MockBouncyPin<'_> {
+        type Error = Infallible;
+    }
+
+    impl embedded_hal::digital::InputPin for MockBouncyPin<'_> {
+        fn is_high(&mut self) -> Result<bool, Self::Error> {
+            Ok(self.level.get())
+        }
+
+        fn is_low(&mut self) -> Result<bool, Self::Error> {
+            Ok(!self.level.get())
+        }
+    }
+
+    #[test]
+    fn test_debouncer() {
+        use PinState::{High, Low};
+
+        let level = Cell::new(false);
+        let clock = Cell::new(Duration::ZERO);
+        let pin = MockBouncyPin { level: &level };
+        let mut debouncer = Debouncer::new(pin, Duration::from_millis(10), || clock.get());
+        let mut sample = |millis: u64, high: bool| {
+            clock.set(Duration::from_millis(millis));
+            level.set(high);
+            debouncer.stable_level().unwrap()
+        };
+
+        // The initial level is reported once it has settled
+        assert_eq!(sample(0, false), None);
+        assert_eq!(sample(5, false), None);
+        assert_eq!(sample(10, false), Some(Low));
+        assert_eq!(sample(15, false), None);
+
+        // Bounces shorter than the window are never reported
+        assert_eq!(sample(20, true), None);
+        assert_eq!(sample(22, false), None);
+        assert_eq!(sample(24, true), None);
+        assert_eq!(sample(27, false), None);
+        assert_eq!(sample(29, true), None);
+        assert_eq!(sample(35, true), None);
+        assert_eq!(sample(39, true), Some(High));
+        assert_eq!(sample(45, true), None);
+
+        // Bouncing back to the stable level reports nothing either
+        assert_eq!(sample(50, false), None);
+        assert_eq!(sample(52, true), None);
+        assert_eq!(sample(70, true), None);
+        assert_eq!(debouncer.level(), Some(High));
+        assert_eq!(debouncer.window(), Duration::from_millis(10));
+
+        let pin = debouncer.release();
+        assert!(pin.level.get());
+    }
+}
@@ -0,0 +1,123 @@
+//! Emoji-specific queries on grapheme clusters.
+
+use crate::Grapheme;
+
+/// Regional indicator symbol letter A, the first of the 26 regional indicators
+const REGIONAL_INDICATOR_A: u32 = 0x1F1E6;
+
+/// Skin tone of an emoji, set by one of the five Fitzpatrick emoji modifiers.
+///
+/// See [`Grapheme::skin_tone`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum SkinTone {
+    /// Light skin tone, `U+1F3FB` (Fitzpatrick types 1-2)
+    Light,
+    /// Medium-light skin tone, `U+1F3FC` (Fitzpatrick type 3)
+    MediumLight,
+    /// Medium skin tone, `U+1F3FD` (Fitzpatrick type 4)
+    Medium,
+    /// Medium-dark skin tone, `U+1F3FE` (Fitzpatrick type 5)
+    MediumDark,
+    /// Dark skin tone, `U+1F3FF` (Fitzpatrick type 6)
+    Dark,
+}
+
+impl SkinTone {
+    /// Returns the skin tone set by an emoji modifier, or `None` for any other character.
+    #[inline]
+    pub fn from_modifier(c: char) -> Option<Self> {
+        match c {
+            '\u{1F3FB}' => Some(Self::Light),
+            '\u{1F3FC}' => Some(Self::MediumLight),
+            '\u{1F3FD}' => Some(Self::Medium),
+            '\u{1F3FE}' => Some(Self::MediumDark),
+            '\u{1F3FF}' => Some(Self::Dark),
+            _ => None,
+        }
+    }
+
+    /// Returns the emoji modifier character setting this skin tone.
+    #[inline]
+    pub fn modifier(self) -> char {
+        match self {
+            Self::Light => '\u{1F3FB}',
+            Self::MediumLight => '\u{1F3FC}',
+            Self::Medium => '\u{1F3FD}',
+            Self::MediumDark => '\u{1F3FE}',
+            Self::Dark => '\u{1F3FF}',
+        }
+    }
+}
+
+/// Maps a regional indicator symbol back to its ASCII uppercase letter.
+#[inline]
+fn regional_letter(c: char) -> Option<char> {
+    let index = (c as u32).checked_sub(REGIONAL_INDICATOR_A)?;
+    (index < 26).then(|| (b'A' + index as u8) as char)
+}
+
+/// Returns the two-letter region code of a flag emoji cluster.
+///
+/// Flags are pairs of regional indicator symbols, each standing for a letter of the ISO 3166-1
+/// alpha-2 country code. Any other cluster, including a lone regional indicator, yields `None`.
+/// The pair isn't checked against the list of assigned country codes.
+///
+/// # Examples
+///
+/// ```
+/// use graphmemes::{flag_code, GraphemeIterator, Result};
+///
+/// # fn main() -> Result<()> {
+/// let mut iter = GraphemeIterator::new("🇺🇸a", false);
+/// assert_eq!(flag_code(&iter.next().unwrap()?), Some(['U', 'S']));
+/// assert_eq!(flag_code(&iter.next().unwrap()?), None);
+/// # Ok(())
+/// # }
+/// ```
+pub fn flag_code<const N: usize>(grapheme: &Grapheme<N>) -> Option<[char; 2]> {
+    match grapheme.as_chars() {
+        &[first, second] => Some([regional_letter(first)?, regional_letter(second)?]),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::GraphemeIterator;
+
+    #[test]
+    fn test_skin_tone_modifiers() {
+        for c in '\u{1F3FB}'..='\u{1F3FF}' {
+            assert_eq!(SkinTone::from_modifier(c).map(SkinTone::modifier), Some(c));
+        }
+        assert_eq!(SkinTone::from_modifier('\u{1F3FA}'), None);
+        assert_eq!(SkinTone::from_modifier('\u{FE0F}'), None);
+    }
+
+    #[test]
+    fn test_flag_code() {
+        let flag = |text: &str| flag_code(&Grapheme::<8>::try_from(text).unwrap());
+        assert_eq!(flag("🇺🇸"), Some(['U', 'S']));
+        assert_eq!(flag("🇬🇧"), Some(['G', 'B']));
+        assert_eq!(flag("🇦🇿"), Some(['A', 'Z']));
+    }
+
+    #[test]
+    fn test_not_flags() {
+        let flag = |text: &str| flag_code(&Grapheme::<8>::try_from(text).unwrap());
+        assert_eq!(flag("🇺"), None);
+        assert_eq!(flag("US"), None);
+        assert_eq!(flag("👋🏽"), None);
+        assert_eq!(flag("🇺🇸🇬"), None);
+    }
+
+    #[test]
+    fn test_iterated_flags() {
+        // A run of three indicators pairs the first two and leaves the last one alone
+        let mut iter = GraphemeIterator::new("🇺🇸🇬", false);
+        assert_eq!(flag_code(&iter.next().unwrap().unwrap()), Some(['U', 'S']));
+        assert_eq!(flag_code(&iter.next().unwrap().unwrap()), None);
+        assert!(iter.next().is_none());
+    }
+}
@@ -1,12 +1,17 @@
 //! Error types for grapheme cluster processing.
 //!
-//! This module provides error types for handling invalid sequences and buffer overflows
-//! that may occur during grapheme cluster iteration. All errors contain precise location
+//! This module provides error types for handling invalid sequences, malformed UTF-8 and
+//! buffer overflows that may occur during grapheme cluster iteration, as well as failed writes
+//! of clusters to an output. All errors contain precise location
 //! information and human-readable help messages.
 
+use core::sync::atomic::{AtomicBool, Ordering};
 use core::{error::Error, fmt};
 use owo_colors::OwoColorize;
 
+/// Whether errors are displayed with colors, see [`GraphemeError::set_color`]
+static COLOR: AtomicBool = AtomicBool::new(true);
+
 /// Errors that can occur during grapheme cluster iteration.
 ///
 /// Each error variant includes the byte offset where the error occurred and
@@ -37,14 +42,37 @@ pub enum GraphemeError {
 
     /// Grapheme cluster exceeds maximum buffer size.
     ///
-    /// This error occurs when a grapheme cluster would require more than
-    /// [`MAX_GRAPHEME_SIZE`](crate::MAX_GRAPHEME_SIZE) code points to represent.
+    /// This error occurs when a grapheme cluster would require more code points than the
+    /// iterator's buffer holds, [`MAX_GRAPHEME_SIZE`](crate::MAX_GRAPHEME_SIZE) by default.
     BufferOverflow {
         /// Starting byte offset where overflow occurred
         offset: usize,
         /// Length in bytes of the sequence that caused overflow
         sequence_len: usize,
     },
+
+    /// Malformed UTF-8 encountered in byte input.
+    ///
+    /// This error occurs when iterating over bytes that aren't valid UTF-8, including a
+    /// multibyte sequence truncated by the end of the input.
+    InvalidUtf8 {
+        /// Starting byte offset of the malformed sequence
+        offset: usize,
+        /// Length in bytes of the malformed sequence
+        sequence_len: usize,
+    },
+
+    /// Writing a grapheme cluster to an output failed.
+    ///
+    /// This error occurs when the [`core::fmt::Write`] output of
+    /// [`write_graphemes`](crate::write_graphemes) or [`coalesce_ansi`](crate::coalesce_ansi)
+    /// rejects a write, for example because its fixed-size buffer is full.
+    WriteFailed {
+        /// Starting byte offset of the sequence that couldn't be written
+        offset: usize,
+        /// Length in bytes of the sequence that couldn't be written
+        sequence_len: usize,
+    },
 }
 
 impl GraphemeError {
@@ -92,6 +120,50 @@ impl GraphemeError {
         }
     }
 
+    /// Creates a new `InvalidUtf8` error.
+    ///
+    /// # Arguments
+    ///
+    /// * `offset` - The byte offset where the malformed sequence starts
+    /// * `sequence_len` - The length in bytes of the malformed sequence
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use graphmemes::GraphemeError;
+    ///
+    /// let err = GraphemeError::invalid_utf8(3, 1);
+    /// ```
+    #[inline]
+    pub fn invalid_utf8(offset: usize, sequence_len: usize) -> Self {
+        Self::InvalidUtf8 {
+            offset,
+            sequence_len,
+        }
+    }
+
+    /// Creates a new `WriteFailed` error.
+    ///
+    /// # Arguments
+    ///
+    /// * `offset` - The byte offset of the sequence that couldn't be written
+    /// * `sequence_len` - The length in bytes of the sequence that couldn't be written
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use graphmemes::GraphemeError;
+    ///
+    /// let err = GraphemeError::write_failed(7, 4);
+    /// ```
+    #[inline]
+    pub fn write_failed(offset: usize, sequence_len: usize) -> Self {
+        Self::WriteFailed {
+            offset,
+            sequence_len,
+        }
+    }
+
     /// Returns the byte offset where the error occurred.
     ///
     /// This offset represents the position in the input string where
@@ -99,25 +171,85 @@ impl GraphemeError {
     #[inline]
     pub fn offset(&self) -> usize {
         match self {
-            Self::InvalidAnsiSequence { offset, .. } | Self::BufferOverflow { offset, .. } => {
-                *offset
-            }
+            Self::InvalidAnsiSequence { offset, .. }
+            | Self::BufferOverflow { offset, .. }
+            | Self::InvalidUtf8 { offset, .. }
+            | Self::WriteFailed { offset, .. } => *offset,
         }
     }
 
+    /// Returns the error with `base` added to its offset.
+    ///
+    /// Offsets are relative to the input of the iterator that produced the error, so when
+    /// iterating a slice of a larger buffer, adding the start of the slice translates them to
+    /// positions in the whole buffer.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use graphmemes::{GraphemeIterator, GraphemeError};
+    ///
+    /// let buffer = b"header: ab\xFF";
+    /// let body = &buffer[8..];
+    /// let err = GraphemeIterator::from_utf8(body, false)
+    ///     .find_map(Result::err)
+    ///     .unwrap()
+    ///     .with_base_offset(8);
+    /// assert_eq!(err, GraphemeError::invalid_utf8(10, 1));
+    /// ```
+    #[inline]
+    pub fn with_base_offset(mut self, base: usize) -> Self {
+        match &mut self {
+            Self::InvalidAnsiSequence { offset, .. }
+            | Self::BufferOverflow { offset, .. }
+            | Self::InvalidUtf8 { offset, .. }
+            | Self::WriteFailed { offset, .. } => *offset += base,
+        }
+        self
+    }
+
     /// Returns the length in bytes of the problematic sequence.
     ///
     /// For invalid ANSI sequences, this is the length of the malformed sequence.
     /// For buffer overflows, this is the length of the sequence that would
-    /// exceed the buffer size.
+    /// exceed the buffer size. For malformed UTF-8, this is the length of the
+    /// malformed byte sequence. For failed writes, this is the length of the
+    /// sequence that couldn't be written.
     #[inline]
     pub fn sequence_length(&self) -> usize {
         match self {
             Self::InvalidAnsiSequence { sequence_len, .. }
-            | Self::BufferOverflow { sequence_len, .. } => *sequence_len,
+            | Self::BufferOverflow { sequence_len, .. }
+            | Self::InvalidUtf8 { sequence_len, .. }
+            | Self::WriteFailed { sequence_len, .. } => *sequence_len,
         }
     }
 
+    /// Enables or disables colors in the [`Display`](fmt::Display) output of all errors.
+    ///
+    /// Errors are displayed with ANSI colors by default, which garbles logs and other output
+    /// that isn't a terminal. When disabled, the same text is displayed without colors.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use graphmemes::GraphemeError;
+    ///
+    /// GraphemeError::set_color(false);
+    /// let display = GraphemeError::invalid_ansi(5, 2).to_string();
+    /// assert!(display.starts_with("Invalid ANSI sequence at offset 5"));
+    /// ```
+    #[inline]
+    pub fn set_color(enabled: bool) {
+        COLOR.store(enabled, Ordering::Relaxed);
+    }
+
+    /// Returns true if errors are displayed with colors, see [`set_color`](Self::set_color).
+    #[inline]
+    pub fn color_enabled() -> bool {
+        COLOR.load(Ordering::Relaxed)
+    }
+
     /// Returns a human-readable error message.
     ///
     /// This message describes the error condition without any formatting
@@ -127,6 +259,8 @@ impl GraphemeError {
         match self {
             Self::InvalidAnsiSequence { .. } => "Invalid ANSI sequence",
             Self::BufferOverflow { .. } => "Grapheme buffer overflow",
+            Self::InvalidUtf8 { .. } => "Invalid UTF-8",
+            Self::WriteFailed { .. } => "Write failed",
         }
     }
 
@@ -139,8 +273,10 @@ impl GraphemeError {
         match self {
             Self::InvalidAnsiSequence { .. } => "ANSI sequences must follow format: \\x1b[<n>m",
             Self::BufferOverflow { .. } => {
-                "Grapheme sequence exceeds maximum supported length (8 code points)"
+                "Grapheme sequence exceeds maximum supported length (8 code points by default)"
             }
+            Self::InvalidUtf8 { .. } => "Input bytes must be valid UTF-8",
+            Self::WriteFailed { .. } => "The output rejected the write, check that it has room left",
         }
     }
 }
@@ -149,19 +285,24 @@ impl fmt::Display for GraphemeError {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         let msg = self.message();
         let help = self.help();
+        let color = Self::color_enabled();
 
-        let colored_msg = msg.red();
-        let colored_msg_bold = colored_msg.bold();
-        let colored_help = help.yellow();
-
-        write!(f, "{}", colored_msg_bold)?;
+        if color {
+            write!(f, "{}", msg.red().bold())?;
+        } else {
+            f.write_str(msg)?;
+        }
         write!(
             f,
             " at offset {} (sequence length {})",
             self.offset(),
             self.sequence_length()
         )?;
-        write!(f, "\nHelp: {}", colored_help)
+        if color {
+            write!(f, "\nHelp: {}", help.yellow())
+        } else {
+            write!(f, "\nHelp: {}", help)
+        }
     }
 }
 
@@ -194,6 +335,30 @@ mod tests {
         let err = GraphemeError::buffer_overflow(20, 5);
         assert_eq!(err.offset(), 20);
         assert_eq!(err.sequence_length(), 5);
+
+        let err = GraphemeError::invalid_utf8(30, 2);
+        assert_eq!(err.offset(), 30);
+        assert_eq!(err.sequence_length(), 2);
+
+        let err = GraphemeError::write_failed(40, 4);
+        assert_eq!(err.offset(), 40);
+        assert_eq!(err.sequence_length(), 4);
+    }
+
+    #[test]
+    fn test_with_base_offset() {
+        let err = GraphemeError::invalid_ansi(3, 2).with_base_offset(100);
+        assert_eq!(err.offset(), 103);
+        assert_eq!(err, GraphemeError::invalid_ansi(103, 2));
+
+        assert_eq!(
+            GraphemeError::buffer_overflow(0, 4).with_base_offset(7),
+            GraphemeError::buffer_overflow(7, 4)
+        );
+        assert_eq!(
+            GraphemeError::write_failed(5, 1).with_base_offset(0),
+            GraphemeError::write_failed(5, 1)
+        );
     }
 
     #[test]
@@ -205,6 +370,14 @@ mod tests {
         let err = GraphemeError::buffer_overflow(0, 1);
         assert!(err.message().contains("buffer"));
         assert!(err.help().contains("maximum"));
+
+        let err = GraphemeError::invalid_utf8(0, 1);
+        assert!(err.message().contains("UTF-8"));
+        assert!(err.help().contains("UTF-8"));
+
+        let err = GraphemeError::write_failed(0, 1);
+        assert!(err.message().contains("Write"));
+        assert!(err.help().contains("room"));
     }
 
     #[test]
@@ -216,6 +389,21 @@ mod tests {
         assert!(display.contains("Help:"));
     }
 
+    #[test]
+    fn test_error_display_without_color() {
+        let err = GraphemeError::buffer_overflow(7, 4);
+        GraphemeError::set_color(false);
+        let plain = make_test_string(&err);
+        GraphemeError::set_color(true);
+        let colored = make_test_string(&err);
+
+        assert!(!plain.contains("\x1b["));
+        assert!(plain.starts_with(err.message()));
+        assert!(plain.ends_with(err.help()));
+        assert!(plain.contains("offset 7"));
+        assert!(colored.contains("\x1b["));
+    }
+
     #[test]
     fn test_error_debug() {
         let err = GraphemeError::buffer_overflow(10, 3);
@@ -11,7 +11,12 @@
 //! The implementation uses bit patterns for efficient boundary detection and fixed-size
 //! buffers to maintain zero allocation guarantees.
 
-use crate::MAX_GRAPHEME_SIZE;
+use core::fmt;
+use core::hash::{Hash, Hasher};
+use core::iter::Copied;
+use core::slice;
+
+use crate::{width, GraphemeError, Result, SkinTone, WidthConfig, MAX_GRAPHEME_SIZE};
 
 /// Unicode grapheme cluster boundary detection rules encoded as bit patterns.
 ///
@@ -31,13 +36,39 @@ pub mod boundary {
     pub const REGIONAL: u32 = 0x10;
     /// Emoji modifiers like skin tones
     pub const EMOJI_MOD: u32 = 0x20;
+    /// Carriage return (`\r`)
+    pub const CR: u32 = 0x40;
+    /// Line feed (`\n`)
+    pub const LF: u32 = 0x80;
+    /// Hangul leading consonant jamo (choseong)
+    pub const HANGUL_L: u32 = 0x100;
+    /// Hangul vowel jamo (jungseong)
+    pub const HANGUL_V: u32 = 0x200;
+    /// Hangul trailing consonant jamo (jongseong)
+    pub const HANGUL_T: u32 = 0x400;
+    /// Precomposed Hangul syllable without a trailing consonant
+    pub const HANGUL_LV: u32 = 0x800;
+    /// Precomposed Hangul syllable with a trailing consonant
+    pub const HANGUL_LVT: u32 = 0x1000;
+    /// Extended pictographic characters, the base of emoji ZWJ sequences
+    pub const PICTOGRAPHIC: u32 = 0x2000;
+    /// Indic consonants that can form conjuncts (`Indic_Conjunct_Break=Consonant`)
+    pub const INCB_CONSONANT: u32 = 0x4000;
+    /// Indic viramas that link consonants into conjuncts (`Indic_Conjunct_Break=Linker`)
+    pub const INCB_LINKER: u32 = 0x8000;
+    /// Control characters other than CR and LF, always a cluster of their own
+    pub const CONTROL: u32 = 0x10000;
 }
 
 /// A fixed-size grapheme cluster representation.
 ///
 /// Stores a sequence of Unicode characters that form a single grapheme cluster.
-/// The size is limited to [`MAX_GRAPHEME_SIZE`] code points to maintain
-/// zero-allocation guarantees while handling complex emoji sequences.
+/// The size is limited to `N` code points to maintain zero-allocation guarantees while
+/// handling complex emoji sequences. `N` defaults to [`MAX_GRAPHEME_SIZE`].
+///
+/// Graphemes produced by [`GraphemeIterator`](crate::GraphemeIterator) also record the
+/// byte span they were read from, see [`byte_offset`](Self::byte_offset) and
+/// [`byte_len`](Self::byte_len).
 ///
 /// # Examples
 ///
@@ -50,16 +81,23 @@ pub mod boundary {
 /// assert_eq!(grapheme.as_chars(), &['\u{0061}', '\u{0301}']);
 /// ```
 #[derive(Debug, Clone, Copy)]
-pub struct Grapheme {
+pub struct Grapheme<const N: usize = MAX_GRAPHEME_SIZE> {
     /// Fixed-size array of characters in the cluster
-    chars: [char; MAX_GRAPHEME_SIZE],
+    chars: [char; N],
     /// Number of valid characters in the array
     len: usize,
+    /// Byte offset of the cluster in the source text
+    offset: usize,
+    /// Length in bytes of the cluster in the source text
+    byte_len: usize,
 }
 
-impl Grapheme {
+impl<const N: usize> Grapheme<N> {
     /// Creates a new grapheme cluster from a fixed-size character array.
     ///
+    /// The cluster isn't associated with any source text, so its byte offset is zero and
+    /// its byte length is the UTF-8 length of its characters.
+    ///
     /// # Arguments
     ///
     /// * `chars` - Fixed-size array of characters
@@ -75,8 +113,71 @@ impl Grapheme {
     /// assert_eq!(grapheme.as_chars(), &['a']);
     /// ```
     #[inline]
-    pub fn new(chars: [char; MAX_GRAPHEME_SIZE], len: usize) -> Self {
-        Self { chars, len }
+    pub fn new(chars: [char; N], len: usize) -> Self {
+        let byte_len = chars[..len].iter().map(|c| c.len_utf8()).sum();
+        Self::with_span(chars, len, 0, byte_len)
+    }
+
+    /// Creates a new grapheme cluster from a sequence of characters.
+    ///
+    /// Like [`new`](Self::new), the cluster isn't associated with any source text, and the
+    /// characters aren't checked to form a single cluster.
+    ///
+    /// # Arguments
+    ///
+    /// * `chars` - The characters of the cluster
+    ///
+    /// # Returns
+    ///
+    /// The grapheme cluster, or a `GraphemeError::BufferOverflow` for the first character
+    /// that doesn't fit in the buffer, with its byte offset in the UTF-8 encoding of
+    /// `chars`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use graphmemes::{Grapheme, Result};
+    ///
+    /// # fn main() -> Result<()> {
+    /// let grapheme: Grapheme = Grapheme::from_chars(['e', '\u{0301}'])?;
+    /// assert_eq!(grapheme, "e\u{0301}");
+    /// assert!(Grapheme::<1>::from_chars(['e', '\u{0301}']).is_err());
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn from_chars(chars: impl IntoIterator<Item = char>) -> Result<Self> {
+        let mut buffer = ['\0'; N];
+        let mut len = 0;
+        let mut byte_len = 0;
+
+        for c in chars {
+            if len == N {
+                return Err(GraphemeError::buffer_overflow(byte_len, c.len_utf8()));
+            }
+            buffer[len] = c;
+            len += 1;
+            byte_len += c.len_utf8();
+        }
+
+        Ok(Self::with_span(buffer, len, 0, byte_len))
+    }
+
+    /// Creates a new grapheme cluster read from a byte span of some source text.
+    ///
+    /// # Arguments
+    ///
+    /// * `chars` - Fixed-size array of characters
+    /// * `len` - Number of valid characters in the array
+    /// * `offset` - Byte offset of the cluster in the source text
+    /// * `byte_len` - Length in bytes of the cluster in the source text
+    #[inline]
+    pub(crate) fn with_span(chars: [char; N], len: usize, offset: usize, byte_len: usize) -> Self {
+        Self {
+            chars,
+            len,
+            offset,
+            byte_len,
+        }
     }
 
     /// Returns a slice of the valid characters in this grapheme cluster.
@@ -97,16 +198,390 @@ impl Grapheme {
 
     /// Returns the number of characters in this grapheme cluster.
     #[inline]
-    pub fn len(&self) -> usize {
+    pub const fn len(&self) -> usize {
         self.len
     }
 
     /// Returns true if this grapheme cluster contains no characters.
     #[inline]
-    pub fn is_empty(&self) -> bool {
+    pub const fn is_empty(&self) -> bool {
         self.len == 0
     }
 
+    /// Returns the first character of this grapheme cluster, or `None` if it's empty.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use graphmemes::{GraphemeIterator, Result};
+    ///
+    /// # fn main() -> Result<()> {
+    /// let grapheme = GraphemeIterator::new("👋🏽", false).next().unwrap()?;
+    /// assert_eq!(grapheme.first(), Some('👋'));
+    /// assert_eq!(grapheme.last(), Some('\u{1F3FD}'));
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[inline]
+    pub const fn first(&self) -> Option<char> {
+        if self.is_empty() {
+            None
+        } else {
+            Some(self.chars[0])
+        }
+    }
+
+    /// Returns the last character of this grapheme cluster, or `None` if it's empty.
+    #[inline]
+    pub const fn last(&self) -> Option<char> {
+        if self.is_empty() {
+            None
+        } else {
+            Some(self.chars[self.len - 1])
+        }
+    }
+
+    /// Returns the base character of this grapheme cluster, or `None` if it's empty.
+    ///
+    /// The base is the character the rest of the cluster modifies or attaches to, such as a
+    /// letter followed by combining marks. This is an alias for [`first`](Self::first).
+    #[inline]
+    pub const fn base(&self) -> Option<char> {
+        self.first()
+    }
+
+    /// Returns an iterator over the characters of this grapheme cluster.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use graphmemes::{GraphemeIterator, Result};
+    ///
+    /// # fn main() -> Result<()> {
+    /// let grapheme = GraphemeIterator::new("e\u{0301}", false).next().unwrap()?;
+    /// let marks = grapheme.chars().filter(|c| ('\u{0300}'..='\u{036F}').contains(c));
+    /// assert_eq!(marks.count(), 1);
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[inline]
+    pub fn chars(&self) -> Copied<slice::Iter<'_, char>> {
+        self.as_chars().iter().copied()
+    }
+
+    /// Returns true if this grapheme cluster contains the character.
+    ///
+    /// Useful for detecting a specific combining mark or modifier within a cluster.
+    #[inline]
+    pub fn contains_char(&self, c: char) -> bool {
+        self.as_chars().contains(&c)
+    }
+
+    /// Encodes this grapheme cluster as UTF-8 into `buf`, returning the encoded string.
+    ///
+    /// This gives a `&str` of the cluster without allocating, for APIs that take string slices.
+    /// Counted ANSI escape sequences are encoded as the lone escape character they're
+    /// represented by.
+    ///
+    /// # Returns
+    ///
+    /// The start of `buf` holding the encoded cluster, or `GraphemeError::WriteFailed` at the
+    /// cluster's offset if `buf` is too small, in which case `buf` is left untouched.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use graphmemes::{GraphemeIterator, Result};
+    ///
+    /// # fn main() -> Result<()> {
+    /// let grapheme = GraphemeIterator::new("e\u{0301}", false).next().unwrap()?;
+    /// let mut buf = [0; 16];
+    /// assert_eq!(grapheme.as_str_in(&mut buf)?, "e\u{0301}");
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn as_str_in<'b>(&self, buf: &'b mut [u8]) -> Result<&'b str> {
+        let len = self.chars().map(char::len_utf8).sum();
+        if len > buf.len() {
+            return Err(GraphemeError::write_failed(self.offset, len));
+        }
+
+        let mut end = 0;
+        for c in self.chars() {
+            end += c.encode_utf8(&mut buf[end..]).len();
+        }
+        Ok(core::str::from_utf8(&buf[..len]).expect("encoded characters are valid UTF-8"))
+    }
+
+    /// Returns the byte offset of this grapheme cluster in the source text.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use graphmemes::{GraphemeIterator, Result};
+    ///
+    /// # fn main() -> Result<()> {
+    /// let text = "a👋b";
+    /// let offsets: Vec<_> = GraphemeIterator::new(text, false)
+    ///     .map(|g| g.map(|g| g.byte_offset()))
+    ///     .collect::<Result<_>>()?;
+    /// assert_eq!(offsets, [0, 1, 5]);
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[inline]
+    pub fn byte_offset(&self) -> usize {
+        self.offset
+    }
+
+    /// Returns the length in bytes of this grapheme cluster in the source text.
+    ///
+    /// For ANSI escape sequences counted as graphemes, this covers the whole sequence.
+    #[inline]
+    pub fn byte_len(&self) -> usize {
+        self.byte_len
+    }
+
+    /// Returns the number of terminal columns this grapheme cluster occupies.
+    ///
+    /// Combining marks, joiners and control characters occupy no columns, wide East Asian
+    /// characters and most emoji occupy two, and everything else occupies one.
+    /// Ambiguous-width characters are treated as narrow, see [`width_with`](Self::width_with)
+    /// for terminals that render them wide.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use graphmemes::{GraphemeIterator, Result};
+    ///
+    /// # fn main() -> Result<()> {
+    /// let widths: Vec<_> = GraphemeIterator::new("a世👋e\u{0301}", false)
+    ///     .map(|g| g.map(|g| g.width()))
+    ///     .collect::<Result<_>>()?;
+    /// assert_eq!(widths, [1, 2, 2, 1]);
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[inline]
+    pub fn width(&self) -> usize {
+        self.width_with(WidthConfig::default())
+    }
+
+    /// Returns the number of terminal columns this grapheme cluster occupies in a CJK context.
+    ///
+    /// Identical to [`width`](Self::width), except ambiguous-width characters such as `§`
+    /// or Greek and Cyrillic letters occupy two columns.
+    #[inline]
+    pub fn width_cjk(&self) -> usize {
+        self.width_with(WidthConfig {
+            ambiguous_wide: true,
+        })
+    }
+
+    /// Returns the number of terminal columns this grapheme cluster occupies.
+    ///
+    /// Like [`width`](Self::width), with ambiguous-width characters treated as `config`
+    /// specifies.
+    ///
+    /// # Arguments
+    ///
+    /// * `config` - How to measure the cluster
+    #[inline]
+    pub fn width_with(&self, config: WidthConfig) -> usize {
+        width::cluster_width(self.as_chars(), config.ambiguous_wide)
+    }
+
+    /// Returns true if this grapheme cluster is written right-to-left.
+    ///
+    /// A cluster is right-to-left if its base character is a strong right-to-left character,
+    /// such as a Hebrew, Arabic or Syriac letter. Digits, punctuation shared with other
+    /// scripts and whitespace are neutral, so they return false, as do all left-to-right
+    /// clusters. Segmentation is unaffected, this only lets bidi renderers group runs
+    /// without classifying the characters again.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use graphmemes::{GraphemeIterator, Result};
+    ///
+    /// # fn main() -> Result<()> {
+    /// let rtl: Vec<_> = GraphemeIterator::new("שלום 1a", false)
+    ///     .map(|g| g.map(|g| g.is_rtl()))
+    ///     .collect::<Result<_>>()?;
+    /// assert_eq!(rtl, [true, true, true, true, false, false, false]);
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[inline]
+    pub fn is_rtl(&self) -> bool {
+        self.base().is_some_and(is_strong_rtl)
+    }
+
+    /// Returns true if this grapheme cluster is an emoji.
+    ///
+    /// A cluster is an emoji if its first character has the `Extended_Pictographic` property,
+    /// which covers single emoji as well as modifier, presentation and ZWJ sequences built on
+    /// them. Flags are made of regional indicators instead, see [`flag_code`](crate::flag_code).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use graphmemes::{GraphemeIterator, Result};
+    ///
+    /// # fn main() -> Result<()> {
+    /// let emoji: Vec<_> = GraphemeIterator::new("a👋🏽👨‍👩‍👧", false)
+    ///     .map(|g| g.map(|g| g.is_emoji_cluster()))
+    ///     .collect::<Result<_>>()?;
+    /// assert_eq!(emoji, [false, true, true]);
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[inline]
+    pub fn is_emoji_cluster(&self) -> bool {
+        self.base().is_some_and(is_extended_pictographic)
+    }
+
+    /// Returns the skin tone of this grapheme cluster.
+    ///
+    /// This is the tone set by the first emoji modifier in the cluster, or `None` if it
+    /// carries no modifier. Emoji of several people, such as `🧑🏻‍🤝‍🧑🏿`, may carry a
+    /// different tone for each of them.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use graphmemes::{GraphemeIterator, Result, SkinTone};
+    ///
+    /// # fn main() -> Result<()> {
+    /// let mut iter = GraphemeIterator::new("👋🏽👋", false);
+    /// assert_eq!(iter.next().unwrap()?.skin_tone(), Some(SkinTone::Medium));
+    /// assert_eq!(iter.next().unwrap()?.skin_tone(), None);
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[inline]
+    pub fn skin_tone(&self) -> Option<SkinTone> {
+        self.as_chars()
+            .iter()
+            .find_map(|&c| SkinTone::from_modifier(c))
+    }
+}
+
+impl<const N: usize> fmt::Display for Grapheme<N> {
+    /// Writes the characters of the cluster in order.
+    ///
+    /// This reproduces the cluster's source text, except for counted ANSI escape sequences,
+    /// which are written as the lone escape character they're represented by.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use graphmemes::{GraphemeIterator, Result};
+    ///
+    /// # fn main() -> Result<()> {
+    /// let grapheme = GraphemeIterator::new("e\u{0301}", false).next().unwrap()?;
+    /// assert_eq!(format!("{grapheme}"), "e\u{0301}");
+    /// # Ok(())
+    /// # }
+    /// ```
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.as_chars()
+            .iter()
+            .try_for_each(|&c| fmt::Write::write_char(f, c))
+    }
+}
+
+impl<const N: usize> TryFrom<&str> for Grapheme<N> {
+    type Error = GraphemeError;
+
+    /// Creates a grapheme cluster from the characters of a string.
+    ///
+    /// See [`Grapheme::from_chars`], the error offset is a byte offset in `text`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use graphmemes::{Grapheme, GraphemeError};
+    ///
+    /// let grapheme = Grapheme::<8>::try_from("👋🏽").unwrap();
+    /// assert_eq!(grapheme.as_chars(), ['👋', '🏽']);
+    ///
+    /// let err = Grapheme::<1>::try_from("👋🏽").unwrap_err();
+    /// assert_eq!(err, GraphemeError::buffer_overflow(4, 4));
+    /// ```
+    #[inline]
+    fn try_from(text: &str) -> Result<Self> {
+        Self::from_chars(text.chars())
+    }
+}
+
+impl<const N: usize> PartialEq for Grapheme<N> {
+    /// Compares the characters of two clusters.
+    ///
+    /// Only the valid characters are compared, so the unused tail of the buffer and the
+    /// span in the source text don't affect equality.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use graphmemes::{GraphemeIterator, Result};
+    ///
+    /// # fn main() -> Result<()> {
+    /// let mut iter = GraphemeIterator::new("👋🏽a👋🏽", false);
+    /// let first = iter.next().unwrap()?;
+    /// assert!(first != iter.next().unwrap()?);
+    /// assert!(first == iter.next().unwrap()?);
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[inline]
+    fn eq(&self, other: &Self) -> bool {
+        self.as_chars() == other.as_chars()
+    }
+}
+
+impl<const N: usize> Eq for Grapheme<N> {}
+
+impl<const N: usize> Hash for Grapheme<N> {
+    /// Hashes the valid characters of the cluster, consistently with [`PartialEq`].
+    #[inline]
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.as_chars().hash(state);
+    }
+}
+
+impl<const N: usize> PartialEq<&str> for Grapheme<N> {
+    /// Compares the characters of the cluster with those of a string.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use graphmemes::{GraphemeIterator, Result};
+    ///
+    /// # fn main() -> Result<()> {
+    /// let grapheme = GraphemeIterator::new("👋🏽", false).next().unwrap()?;
+    /// assert!(grapheme == "👋🏽");
+    /// assert!(grapheme != "👋");
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[inline]
+    fn eq(&self, other: &&str) -> bool {
+        self.as_chars().iter().copied().eq(other.chars())
+    }
+}
+
+impl<const N: usize> PartialEq<char> for Grapheme<N> {
+    /// Compares a single-character cluster with a character.
+    ///
+    /// Clusters of any other length never compare equal.
+    #[inline]
+    fn eq(&self, other: &char) -> bool {
+        self.as_chars() == [*other]
+    }
+}
+
+impl Grapheme {
     /// Determines the boundary category of a character for grapheme clustering.
     ///
     /// This function categorizes characters according to UAX #29 rules using
@@ -128,7 +603,15 @@ impl Grapheme {
             '\u{FE0F}' => EMOJI_MOD,
             '\u{1F3FB}'..='\u{1F3FF}' => EMOJI_MOD,
             '\u{1F1E6}'..='\u{1F1FF}' => REGIONAL,
+            '\r' => CR,
+            '\n' => LF,
+            c if c.is_ascii_control() => CONTROL,
             c if c.is_ascii() => 0,
+            c if is_control(c) => CONTROL,
+            c if is_hangul(c) => hangul_category(c),
+            c if is_extended_pictographic(c) => PICTOGRAPHIC,
+            c if is_indic_linker(c) => INCB_LINKER,
+            c if is_indic_consonant(c) => INCB_CONSONANT,
             c if is_extend(c) => EXTEND,
             c if is_spacing_mark(c) => SPACINGMARK,
             c if is_prepend(c) => PREPEND,
@@ -137,16 +620,193 @@ impl Grapheme {
     }
 }
 
-/// Determines if a character is an emoji.
+/// Determines if a character is an extended pictographic.
+///
+/// Checks if the character has the `Extended_Pictographic` property, covering emoji,
+/// pictographic symbols, and the code point ranges reserved for future emoji.
+/// Emoji modifiers (skin tones) and regional indicators are excluded, as they have their
+/// own roles in grapheme clustering.
+#[inline]
+pub(crate) fn is_extended_pictographic(c: char) -> bool {
+    matches!(c,
+        '\u{00A9}' | '\u{00AE}' |                 // Copyright, registered
+        '\u{203C}' | '\u{2049}' |                 // Double exclamation, exclamation question
+        '\u{2122}' | '\u{2139}' |                 // Trade mark, information
+        '\u{2194}'..='\u{2199}' |                 // Arrows
+        '\u{21A9}'..='\u{21AA}' |                 // Hooked arrows
+        '\u{231A}'..='\u{231B}' |                 // Watch, hourglass
+        '\u{2328}' | '\u{2388}' | '\u{23CF}' |    // Keyboard, helm symbol, eject
+        '\u{23E9}'..='\u{23F3}' |                 // Media controls, clocks
+        '\u{23F8}'..='\u{23FA}' |                 // Media controls
+        '\u{24C2}' |                              // Circled M
+        '\u{25AA}'..='\u{25AB}' |                 // Small squares
+        '\u{25B6}' | '\u{25C0}' |                 // Play and reverse buttons
+        '\u{25FB}'..='\u{25FE}' |                 // Medium squares
+        '\u{2600}'..='\u{2605}' |                 // Misc symbols
+        '\u{2607}'..='\u{2612}' |                 // Misc symbols
+        '\u{2614}'..='\u{2685}' |                 // Misc symbols
+        '\u{2690}'..='\u{2705}' |                 // Misc symbols, dingbats
+        '\u{2708}'..='\u{2712}' |                 // Dingbats
+        '\u{2714}' | '\u{2716}' | '\u{271D}' |    // Check mark, multiply, cross
+        '\u{2721}' | '\u{2728}' |                 // Star of David, sparkles
+        '\u{2733}'..='\u{2734}' |                 // Eight spoked asterisk and star
+        '\u{2744}' | '\u{2747}' |                 // Snowflake, sparkle
+        '\u{274C}' | '\u{274E}' |                 // Cross marks
+        '\u{2753}'..='\u{2755}' | '\u{2757}' |    // Question and exclamation marks
+        '\u{2763}'..='\u{2767}' |                 // Heart exclamation, hearts
+        '\u{2795}'..='\u{2797}' |                 // Heavy plus, minus, division
+        '\u{27A1}' | '\u{27B0}' | '\u{27BF}' |    // Right arrow, curly loops
+        '\u{2934}'..='\u{2935}' |                 // Curved arrows
+        '\u{2B05}'..='\u{2B07}' |                 // Arrows
+        '\u{2B1B}'..='\u{2B1C}' |                 // Large squares
+        '\u{2B50}' | '\u{2B55}' |                 // Star, heavy large circle
+        '\u{3030}' | '\u{303D}' |                 // Wavy dash, part alternation mark
+        '\u{3297}' | '\u{3299}' |                 // Circled ideographs
+        '\u{1F000}'..='\u{1F0FF}' |               // Mahjong, domino and playing cards
+        '\u{1F10D}'..='\u{1F10F}' |               // Circled zero with slash
+        '\u{1F12F}' |                             // Copyleft
+        '\u{1F16C}'..='\u{1F171}' |               // Raised marks, negative squared letters
+        '\u{1F17E}'..='\u{1F17F}' |               // Negative squared O and P
+        '\u{1F18E}' |                             // Negative squared AB
+        '\u{1F191}'..='\u{1F19A}' |               // Squared CL through VS
+        '\u{1F1AD}'..='\u{1F1E5}' |               // Reserved before regional indicators
+        '\u{1F201}'..='\u{1F20F}' |               // Squared katakana
+        '\u{1F21A}' | '\u{1F22F}' |               // Squared ideographs
+        '\u{1F232}'..='\u{1F23A}' |               // Squared ideographs
+        '\u{1F23C}'..='\u{1F23F}' |               // Reserved
+        '\u{1F249}'..='\u{1F3FA}' |               // Emoji up to skin tone modifiers
+        '\u{1F400}'..='\u{1F53D}' |               // Animals, people, objects
+        '\u{1F546}'..='\u{1F64F}' |               // Objects, emoticons
+        '\u{1F680}'..='\u{1F6FF}' |               // Transport and map symbols
+        '\u{1F774}'..='\u{1F77F}' |               // Alchemical symbols
+        '\u{1F7D5}'..='\u{1F7FF}' |               // Geometric shapes extended
+        '\u{1F80C}'..='\u{1F80F}' |               // Reserved arrows
+        '\u{1F848}'..='\u{1F84F}' |               // Reserved arrows
+        '\u{1F85A}'..='\u{1F85F}' |               // Reserved arrows
+        '\u{1F888}'..='\u{1F88F}' |               // Reserved arrows
+        '\u{1F8AE}'..='\u{1F8FF}' |               // Reserved arrows
+        '\u{1F90C}'..='\u{1F93A}' |               // Supplemental symbols and pictographs
+        '\u{1F93C}'..='\u{1F945}' |               // Supplemental symbols and pictographs
+        '\u{1F947}'..='\u{1FAFF}' |               // Supplemental and extended-A pictographs
+        '\u{1FC00}'..='\u{1FFFD}'                 // Reserved for future emoji
+    )
+}
+
+/// Determines if a non-ASCII character is a control character.
+///
+/// Covers the C1 controls, line and paragraph separators and the format characters that
+/// UAX #29 treats as controls. Zero width joiners and non-joiners, prepended concatenation
+/// marks and emoji tags are excluded, as they have their own roles in grapheme clustering.
+#[inline]
+fn is_control(c: char) -> bool {
+    matches!(c,
+        '\u{0080}'..='\u{009F}' |    // C1 controls
+        '\u{00AD}' |                 // Soft hyphen
+        '\u{061C}' |                 // Arabic letter mark
+        '\u{180E}' |                 // Mongolian vowel separator
+        '\u{200B}' |                 // Zero width space
+        '\u{200E}'..='\u{200F}' |    // Left-to-right and right-to-left marks
+        '\u{2028}'..='\u{202E}' |    // Line and paragraph separators, bidi embeddings
+        '\u{2060}'..='\u{206F}' |    // Word joiner, invisible operators, bidi isolates
+        '\u{FEFF}' |                 // Zero width no-break space
+        '\u{FFF0}'..='\u{FFFB}' |    // Interlinear annotations
+        '\u{13430}'..='\u{1343F}' |  // Egyptian hieroglyph format controls
+        '\u{1BCA0}'..='\u{1BCA3}' |  // Shorthand format controls
+        '\u{1D173}'..='\u{1D17A}' |  // Musical symbol format controls
+        '\u{E0000}'..='\u{E001F}' |  // Tag block before the emoji tags
+        '\u{E0080}'..='\u{E00FF}' |  // Tag block after the emoji tags
+        '\u{E01F0}'..='\u{E0FFF}'    // Tag block after the variation selectors
+    )
+}
+
+/// First precomposed Hangul syllable (`가`).
+const HANGUL_SYLLABLE_BASE: u32 = 0xAC00;
+/// Number of trailing consonant slots per precomposed Hangul syllable, including "none".
+const HANGUL_T_COUNT: u32 = 28;
+
+/// Determines if a character is a Hangul jamo or precomposed syllable.
+#[inline]
+fn is_hangul(c: char) -> bool {
+    matches!(c,
+        '\u{1100}'..='\u{11FF}' |  // Hangul Jamo
+        '\u{A960}'..='\u{A97C}' |  // Hangul Jamo Extended-A
+        '\u{AC00}'..='\u{D7A3}' |  // Hangul Syllables
+        '\u{D7B0}'..='\u{D7FB}'    // Hangul Jamo Extended-B
+    )
+}
+
+/// Determines the Hangul syllable type of a character.
+///
+/// Precomposed syllables are laid out in blocks of [`HANGUL_T_COUNT`], where the first
+/// syllable of each block has no trailing consonant (LV) and the rest do (LVT).
+/// Characters inside the Hangul blocks that are unassigned map to zero.
+#[inline]
+fn hangul_category(c: char) -> u32 {
+    use boundary::*;
+    match c {
+        '\u{1100}'..='\u{115F}' | '\u{A960}'..='\u{A97C}' => HANGUL_L,
+        '\u{1160}'..='\u{11A7}' | '\u{D7B0}'..='\u{D7C6}' => HANGUL_V,
+        '\u{11A8}'..='\u{11FF}' | '\u{D7CB}'..='\u{D7FB}' => HANGUL_T,
+        '\u{AC00}'..='\u{D7A3}'
+            if (c as u32 - HANGUL_SYLLABLE_BASE).is_multiple_of(HANGUL_T_COUNT) =>
+        {
+            HANGUL_LV
+        }
+        '\u{AC00}'..='\u{D7A3}' => HANGUL_LVT,
+        _ => 0,
+    }
+}
+
+/// Determines if a character is an Indic virama that links consonants into conjuncts.
 ///
-/// Checks if the character falls within the Unicode ranges designated for emoji
-/// symbols, including basic emoji, symbol emoji, and dingbats.
+/// Covers the characters with `Indic_Conjunct_Break=Linker`. They also extend the
+/// preceding character like any other combining mark.
 #[inline]
-pub(crate) fn is_emoji(c: char) -> bool {
+fn is_indic_linker(c: char) -> bool {
+    matches!(
+        c,
+        '\u{094D}' |  // Devanagari sign virama
+        '\u{09CD}' |  // Bengali sign virama
+        '\u{0ACD}' |  // Gujarati sign virama
+        '\u{0B4D}' |  // Oriya sign virama
+        '\u{0C4D}' |  // Telugu sign virama
+        '\u{0D4D}' // Malayalam sign virama
+    )
+}
+
+/// Determines if a character is an Indic consonant that can form conjuncts.
+///
+/// Covers the characters with `Indic_Conjunct_Break=Consonant`, the consonants of the
+/// scripts whose viramas are linkers.
+#[inline]
+fn is_indic_consonant(c: char) -> bool {
     matches!(c,
-        '\u{1F300}'..='\u{1F9FF}' | // Emoji
-        '\u{2600}'..='\u{26FF}' |   // Misc symbols
-        '\u{2700}'..='\u{27BF}'     // Dingbats
+        '\u{0915}'..='\u{0939}' |  // Devanagari consonants
+        '\u{0958}'..='\u{095F}' |  // Devanagari consonants with nukta
+        '\u{0978}'..='\u{097F}' |  // Devanagari additional consonants
+        '\u{0995}'..='\u{09A8}' |  // Bengali consonants
+        '\u{09AA}'..='\u{09B0}' |
+        '\u{09B2}' |
+        '\u{09B6}'..='\u{09B9}' |
+        '\u{09DC}'..='\u{09DD}' |  // Bengali consonants with nukta
+        '\u{09DF}' |
+        '\u{09F0}'..='\u{09F1}' |  // Assamese consonants
+        '\u{0A95}'..='\u{0AA8}' |  // Gujarati consonants
+        '\u{0AAA}'..='\u{0AB0}' |
+        '\u{0AB2}'..='\u{0AB3}' |
+        '\u{0AB5}'..='\u{0AB9}' |
+        '\u{0AF9}' |
+        '\u{0B15}'..='\u{0B28}' |  // Oriya consonants
+        '\u{0B2A}'..='\u{0B30}' |
+        '\u{0B32}'..='\u{0B33}' |
+        '\u{0B35}'..='\u{0B39}' |
+        '\u{0B5C}'..='\u{0B5D}' |  // Oriya consonants with nukta
+        '\u{0B5F}' |
+        '\u{0B71}' |
+        '\u{0C15}'..='\u{0C28}' |  // Telugu consonants
+        '\u{0C2A}'..='\u{0C39}' |
+        '\u{0C58}'..='\u{0C5A}' |
+        '\u{0D15}'..='\u{0D3A}'    // Malayalam consonants
     )
 }
 
@@ -158,11 +818,23 @@ pub(crate) fn is_emoji(c: char) -> bool {
 #[inline]
 fn is_extend(c: char) -> bool {
     matches!(c,
-        '\u{0300}'..='\u{036F}' |  // Combining marks
-        '\u{1AB0}'..='\u{1AFF}' |  // Extended combining marks
-        '\u{1DC0}'..='\u{1DFF}' |  // Supplement combining marks
-        '\u{20D0}'..='\u{20FF}' |  // Combining marks for symbols
-        '\u{FE20}'..='\u{FE2F}'    // Combining half marks
+        '\u{0300}'..='\u{036F}' |    // Combining marks
+        '\u{0900}'..='\u{0902}' |    // Devanagari candrabindu, anusvara
+        '\u{093A}' | '\u{093C}' |    // Devanagari vowel sign oe, nukta
+        '\u{0941}'..='\u{0948}' |    // Devanagari vowel signs
+        '\u{0951}'..='\u{0957}' |    // Devanagari stress signs
+        '\u{0962}'..='\u{0963}' |    // Devanagari vowel signs vocalic
+        '\u{0981}' | '\u{09BC}' |    // Bengali candrabindu, nukta
+        '\u{09C1}'..='\u{09C4}' |    // Bengali vowel signs
+        '\u{09E2}'..='\u{09E3}' |    // Bengali vowel signs vocalic
+        '\u{180B}'..='\u{180D}' |    // Mongolian free variation selectors
+        '\u{180F}' |                 // Mongolian free variation selector four
+        '\u{1AB0}'..='\u{1AFF}' |    // Extended combining marks
+        '\u{1DC0}'..='\u{1DFF}' |    // Supplement combining marks
+        '\u{20D0}'..='\u{20FF}' |    // Combining marks for symbols
+        '\u{FE00}'..='\u{FE0E}' |    // Variation selectors
+        '\u{FE20}'..='\u{FE2F}' |    // Combining half marks
+        '\u{E0100}'..='\u{E01EF}'    // Variation selectors supplement
     )
 }
 
@@ -195,3 +867,238 @@ fn is_prepend(c: char) -> bool {
         '\u{08E2}'                 // Arabic Disputed End Of Ayah
     )
 }
+
+/// Determines if a character is a strong right-to-left character.
+///
+/// Checks if the character has the `R` or `AL` bidirectional class in the Hebrew, Arabic,
+/// Syriac and Thaana scripts and their presentation forms. Combining marks and digits are
+/// excluded, as they take their direction from the surrounding text.
+#[inline]
+fn is_strong_rtl(c: char) -> bool {
+    matches!(c,
+        '\u{05BE}' | '\u{05C0}' | '\u{05C3}' |    // Hebrew punctuation
+        '\u{05C6}' |                              // Hebrew punctuation nun hafukha
+        '\u{05D0}'..='\u{05EA}' |                 // Hebrew letters
+        '\u{05EF}'..='\u{05F4}' |                 // Hebrew ligatures and punctuation
+        '\u{0608}' | '\u{060B}' | '\u{060D}' |    // Arabic ray, afghani sign, date separator
+        '\u{061B}'..='\u{064A}' |                 // Arabic punctuation and letters
+        '\u{066D}'..='\u{066F}' |                 // Arabic five pointed star, dotless letters
+        '\u{0671}'..='\u{06D5}' |                 // Arabic extended letters
+        '\u{06E5}'..='\u{06E6}' |                 // Arabic small waw and yeh
+        '\u{06EE}'..='\u{06EF}' |                 // Arabic letters with inverted v
+        '\u{06FA}'..='\u{070F}' |                 // Arabic letters, Syriac punctuation
+        '\u{0710}' | '\u{0712}'..='\u{072F}' |    // Syriac letters
+        '\u{074D}'..='\u{07A5}' |                 // Syriac, Arabic supplement, Thaana letters
+        '\u{07B1}' |                              // Thaana letter naa
+        '\u{0860}'..='\u{086A}' |                 // Syriac supplement
+        '\u{0870}'..='\u{088E}' |                 // Arabic extended-B letters
+        '\u{08A0}'..='\u{08C9}' |                 // Arabic extended-A letters
+        '\u{FB1D}' | '\u{FB1F}'..='\u{FB28}' |    // Hebrew presentation forms
+        '\u{FB2A}'..='\u{FB4F}' |                 // Hebrew presentation forms
+        '\u{FB50}'..='\u{FD3D}' |                 // Arabic presentation forms-A
+        '\u{FD50}'..='\u{FDC7}' |                 // Arabic ligatures
+        '\u{FDF0}'..='\u{FDFC}' |                 // Arabic ligatures
+        '\u{FE70}'..='\u{FEFC}' |                 // Arabic presentation forms-B
+        '\u{1EE00}'..='\u{1EEBB}'                 // Arabic mathematical alphabetic symbols
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Grapheme, GraphemeError, GraphemeIterator, SkinTone, MAX_GRAPHEME_SIZE};
+    use core::fmt::Write;
+    use heapless::{FnvIndexMap, String};
+
+    #[test]
+    fn test_display() {
+        for input in ["👋", "e\u{0301}", "👨\u{200D}👩\u{200D}👧", "\r\n"] {
+            let grapheme = GraphemeIterator::new(input, false).next().unwrap().unwrap();
+            let mut output = String::<32>::new();
+            write!(output, "{grapheme}").unwrap();
+            assert_eq!(output, input);
+        }
+    }
+
+    #[test]
+    fn test_eq_str() {
+        let grapheme = GraphemeIterator::new("é", false).next().unwrap().unwrap();
+        assert!(grapheme == "é");
+        assert!(grapheme != "e");
+        assert!(grapheme != "é\u{0301}");
+
+        let grapheme = GraphemeIterator::new("e\u{0301}", false)
+            .next()
+            .unwrap()
+            .unwrap();
+        assert!(grapheme == "e\u{0301}");
+        assert!(grapheme != "é");
+    }
+
+    #[test]
+    fn test_eq_char() {
+        let grapheme = GraphemeIterator::new("a", false).next().unwrap().unwrap();
+        assert!(grapheme == 'a');
+        assert!(grapheme != 'b');
+        assert!(grapheme != "ab");
+
+        let grapheme = GraphemeIterator::new("👋🏽", false).next().unwrap().unwrap();
+        assert!(grapheme != '👋');
+    }
+
+    #[test]
+    fn test_from_chars() {
+        let grapheme = Grapheme::<8>::from_chars("e\u{0301}".chars()).unwrap();
+        assert_eq!(grapheme.as_chars(), ['e', '\u{0301}']);
+        assert_eq!(grapheme.byte_len(), 3);
+
+        let grapheme = Grapheme::<8>::from_chars([]).unwrap();
+        assert!(grapheme.is_empty());
+
+        let err = Grapheme::<2>::from_chars(['a', 'é', '\u{0301}']).unwrap_err();
+        assert_eq!(err, GraphemeError::buffer_overflow(3, 2));
+    }
+
+    #[test]
+    fn test_try_from_str() {
+        let grapheme = Grapheme::<8>::try_from("é").unwrap();
+        assert_eq!(grapheme, "é");
+        assert_eq!(grapheme.byte_offset(), 0);
+        assert_eq!(grapheme.byte_len(), 2);
+
+        let text = "a\u{0301}\u{0302}\u{0303}\u{0304}\u{0305}\u{0306}\u{0307}\u{0308}";
+        let err = Grapheme::<MAX_GRAPHEME_SIZE>::try_from(text).unwrap_err();
+        assert_eq!(err, GraphemeError::buffer_overflow(15, 2));
+        assert!(Grapheme::<16>::try_from(text).is_ok());
+    }
+
+    #[test]
+    fn test_eq() {
+        let mut chars = ['x'; MAX_GRAPHEME_SIZE];
+        chars[0] = 'é';
+        let padded = Grapheme::new(chars, 1);
+        let grapheme = GraphemeIterator::new("aé", false).nth(1).unwrap().unwrap();
+        assert_eq!(grapheme, padded);
+        assert_ne!(grapheme, Grapheme::new(chars, 2));
+    }
+
+    #[test]
+    fn test_hash_map_key() {
+        let text = "👨\u{200D}👩\u{200D}👧 👨\u{200D}👩\u{200D}👧";
+        let mut counts = FnvIndexMap::<Grapheme, usize, 4>::new();
+        for grapheme in GraphemeIterator::new(text, false) {
+            let grapheme = grapheme.unwrap();
+            match counts.get_mut(&grapheme) {
+                Some(count) => *count += 1,
+                None => {
+                    counts.insert(grapheme, 1).unwrap();
+                }
+            }
+        }
+
+        assert_eq!(counts.len(), 2);
+        let family = GraphemeIterator::new(text, false).next().unwrap().unwrap();
+        assert_eq!(counts[&family], 2);
+    }
+
+    #[test]
+    fn test_first_last() {
+        let family = Grapheme::<8>::try_from("👨\u{200D}👩\u{200D}👧").unwrap();
+        assert_eq!(family.first(), Some('👨'));
+        assert_eq!(family.last(), Some('👧'));
+        assert_eq!(family.base(), Some('👨'));
+
+        let accented = Grapheme::<8>::try_from("e\u{0301}").unwrap();
+        assert_eq!(accented.base(), Some('e'));
+        assert_eq!(accented.last(), Some('\u{0301}'));
+
+        let single = Grapheme::<8>::try_from("a").unwrap();
+        assert_eq!(single.first(), single.last());
+
+        let empty = Grapheme::new(['\0'; MAX_GRAPHEME_SIZE], 0);
+        assert_eq!(empty.first(), None);
+        assert_eq!(empty.last(), None);
+        assert_eq!(empty.base(), None);
+    }
+
+    #[test]
+    fn test_chars() {
+        let accented = Grapheme::<8>::try_from("e\u{0301}").unwrap();
+        assert!(accented.contains_char('\u{0301}'));
+        assert!(accented.contains_char('e'));
+        assert!(!accented.contains_char('z'));
+        assert_eq!(accented.chars().count(), 2);
+        assert!(accented.chars().eq(['e', '\u{0301}']));
+        assert!(accented.chars().rev().eq(['\u{0301}', 'e']));
+
+        // Only the valid prefix of the buffer is visible
+        let empty = Grapheme::new(['\0'; MAX_GRAPHEME_SIZE], 0);
+        assert_eq!(empty.chars().next(), None);
+        assert!(!empty.contains_char('\0'));
+    }
+
+    #[test]
+    fn test_as_str_in() {
+        let wave = Grapheme::<8>::try_from("👋").unwrap();
+        let mut buf = [0; 8];
+        assert_eq!(wave.as_str_in(&mut buf), Ok("👋"));
+        assert_eq!(&buf[4..], [0; 4]);
+
+        let toned = Grapheme::<8>::try_from("👋🏽").unwrap();
+        let mut buf = [0; 8];
+        assert_eq!(toned.as_str_in(&mut buf), Ok("👋🏽"));
+
+        let mut iter = GraphemeIterator::new("ae\u{0301}", false);
+        iter.next();
+        let accented = iter.next().unwrap().unwrap();
+        let mut buf = [0; 2];
+        assert_eq!(
+            accented.as_str_in(&mut buf),
+            Err(GraphemeError::write_failed(1, 3))
+        );
+        assert_eq!(buf, [0; 2]);
+
+        let empty = Grapheme::new(['\0'; MAX_GRAPHEME_SIZE], 0);
+        assert_eq!(empty.as_str_in(&mut []), Ok(""));
+    }
+
+    #[test]
+    fn test_emoji_queries() {
+        let wave = Grapheme::<8>::try_from("👋").unwrap();
+        assert!(wave.is_emoji_cluster());
+        assert_eq!(wave.skin_tone(), None);
+
+        let toned = Grapheme::<8>::try_from("👋🏽").unwrap();
+        assert!(toned.is_emoji_cluster());
+        assert_eq!(toned.skin_tone(), Some(SkinTone::Medium));
+
+        let letter = Grapheme::<8>::try_from("a").unwrap();
+        assert!(!letter.is_emoji_cluster());
+        assert_eq!(letter.skin_tone(), None);
+
+        // Flags and lone modifiers aren't pictographic
+        assert!(!Grapheme::<8>::try_from("🇺🇸").unwrap().is_emoji_cluster());
+        let modifier = Grapheme::<8>::try_from("\u{1F3FF}").unwrap();
+        assert!(!modifier.is_emoji_cluster());
+        assert_eq!(modifier.skin_tone(), Some(SkinTone::Dark));
+    }
+
+    #[test]
+    fn test_is_rtl() {
+        // Words from the RTL benchmark sample
+        for cluster in GraphemeIterator::new("بالعالم العربي كيف حالك؟", false) {
+            let cluster = cluster.unwrap();
+            assert_eq!(cluster.is_rtl(), cluster != ' ', "{:?}", cluster.as_chars());
+        }
+
+        for text in ["שָׁלוֹם", "ܫܠܡܐ", "ﻻ"] {
+            let cluster = GraphemeIterator::new(text, false).next().unwrap().unwrap();
+            assert!(cluster.is_rtl(), "{text}");
+        }
+
+        for text in ["Hello", "e\u{0301}", "١", "👋🏽", "\u{064B}"] {
+            let cluster = GraphemeIterator::new(text, false).next().unwrap().unwrap();
+            assert!(!cluster.is_rtl(), "{text}");
+        }
+        assert!(!Grapheme::new(['\0'; MAX_GRAPHEME_SIZE], 0).is_rtl());
+    }
+}
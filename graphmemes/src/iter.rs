@@ -11,17 +11,95 @@
 //! The implementation follows Unicode Standard Annex #29 (UAX #29) for grapheme cluster
 //! boundaries and supports extended grapheme clusters.
 
-use crate::{boundary, grapheme::is_emoji, Grapheme, GraphemeError, Result, MAX_GRAPHEME_SIZE};
-use core::str::Chars;
-
-/// Bit mask for extracting the state bits from the state byte
-const STATE_MASK: u8 = 0b111;
-/// Initial state for the iterator
-const STATE_START: u8 = 0;
-/// State indicating the iterator is processing a grapheme cluster
-const STATE_IN_GRAPHEME: u8 = 1;
-/// State indicating the iterator is processing an ANSI escape sequence
-const STATE_IN_ANSI: u8 = 2;
+use crate::{boundary, Grapheme, GraphemeError, Result, MAX_GRAPHEME_SIZE};
+
+/// Flag bit indicating ANSI escape sequences are counted as graphemes
+const FLAG_COUNT_ANSI: u8 = 0b1;
+/// Flag bit indicating oversized clusters are split instead of producing an error
+const FLAG_LOSSY: u8 = 0b10;
+
+/// Escape character that introduces an ANSI escape sequence
+const ESC: char = '\x1b';
+/// Bell character, one of the terminators of an OSC sequence
+const BEL: char = '\x07';
+
+/// A unit of input produced by scanning forward from a segment boundary.
+enum Segment<const N: usize> {
+    /// A complete grapheme cluster
+    Grapheme(Grapheme<N>),
+    /// A terminated ANSI escape sequence
+    Ansi,
+    /// An ANSI escape sequence cut short by another escape or the end of input
+    Unterminated,
+    /// A sequence that could not be processed
+    Invalid(GraphemeError),
+}
+
+/// Context carried through a grapheme cluster for boundary rules that depend on more than
+/// the previous character.
+#[derive(Debug, Clone, Copy, Default)]
+struct ClusterContext {
+    /// The cluster so far ends in `Extended_Pictographic Extend*`
+    pictographic: bool,
+    /// The cluster so far ends in `Extended_Pictographic Extend* ZWJ` (GB11)
+    pictographic_zwj: bool,
+    /// The cluster so far ends in an odd number of regional indicators (GB12, GB13)
+    regional_odd: bool,
+    /// The cluster so far ends in an Indic consonant followed by extending marks and linkers
+    indic_consonant: bool,
+    /// As `indic_consonant`, with at least one linker after the consonant (GB9c)
+    indic_linked: bool,
+}
+
+impl ClusterContext {
+    /// A context in which every context-dependent rule allows joining.
+    ///
+    /// A boundary found in this context holds no matter what precedes the character pair.
+    const JOINING: Self = Self {
+        pictographic: true,
+        pictographic_zwj: true,
+        regional_odd: true,
+        indic_consonant: true,
+        indic_linked: true,
+    };
+
+    /// Updates the context with the category of the next character in the cluster.
+    #[inline]
+    fn advance(&mut self, category: u32) {
+        match category {
+            boundary::PICTOGRAPHIC => {
+                *self = Self {
+                    pictographic: true,
+                    ..Self::default()
+                };
+            }
+            boundary::EXTEND | boundary::EMOJI_MOD => {
+                self.pictographic_zwj = false;
+            }
+            boundary::INCB_LINKER => {
+                self.pictographic_zwj = false;
+                self.indic_linked = self.indic_consonant;
+            }
+            boundary::INCB_CONSONANT => {
+                *self = Self {
+                    indic_consonant: true,
+                    ..Self::default()
+                };
+            }
+            boundary::ZWJ => {
+                self.pictographic_zwj = self.pictographic;
+                self.pictographic = false;
+            }
+            boundary::REGIONAL => {
+                *self = Self {
+                    regional_odd: !self.regional_odd,
+                    ..Self::default()
+                };
+            }
+            _ => *self = Self::default(),
+        }
+    }
+}
 
 /// Zero-allocation iterator for Unicode grapheme clusters.
 ///
@@ -34,6 +112,9 @@ const STATE_IN_ANSI: u8 = 2;
 /// - Zero Width Joiner (ZWJ) sequences
 /// - ANSI escape sequences (optionally counted as graphemes)
 ///
+/// The iterator is double-ended, so clusters can also be taken from the end of the text.
+/// Both directions agree on cluster boundaries for the same input.
+///
 /// # Examples
 ///
 /// ```
@@ -59,24 +140,62 @@ const STATE_IN_ANSI: u8 = 2;
 /// # Ok(())
 /// # }
 /// ```
-pub struct GraphemeIterator<'a> {
-    /// Character iterator over the input text
-    chars: Chars<'a>,
-    /// Current byte position in the input string
-    position: usize,
-    /// Fixed-size buffer for accumulating grapheme clusters
-    buffer: [char; MAX_GRAPHEME_SIZE],
-    /// Number of characters currently in the buffer
-    buffer_len: usize,
-    /// Combined state byte: lower 3 bits for state, bit 3 for ANSI counting
-    state: u8,
-    /// Previous character category for boundary detection
-    prev_category: u32,
+///
+/// With a larger cluster buffer:
+///
+/// ```
+/// use graphmemes::{GraphemeIterator, Result};
+///
+/// # fn main() -> Result<()> {
+/// let text = "a\u{0301}\u{0302}\u{0303}\u{0304}\u{0305}\u{0306}\u{0307}\u{0308}\u{0309}";
+/// assert!(GraphemeIterator::new(text, false).next().unwrap().is_err());
+///
+/// let mut iter = GraphemeIterator::<16>::with_buffer_size(text, false);
+/// assert_eq!(iter.next().transpose()?.unwrap().len(), 10);
+/// # Ok(())
+/// # }
+/// ```
+///
+/// Iterating from the end:
+///
+/// ```
+/// use graphmemes::{GraphemeIterator, Result};
+///
+/// # fn main() -> Result<()> {
+/// let mut iter = GraphemeIterator::new("ab👋🏽", false);
+/// assert_eq!(iter.next_back().transpose()?.unwrap().as_chars(), &['👋', '🏽']);
+/// assert_eq!(iter.next().transpose()?.unwrap().as_chars(), &['a']);
+/// assert_eq!(iter.next_back().transpose()?.unwrap().as_chars(), &['b']);
+/// assert!(iter.next().is_none());
+/// # Ok(())
+/// # }
+/// ```
+pub struct GraphemeIterator<'a, const N: usize = MAX_GRAPHEME_SIZE> {
+    /// The input text
+    text: &'a str,
+    /// Byte offset of the start of the unconsumed input, always on a segment boundary
+    front: usize,
+    /// Byte offset of the end of the unconsumed input, always on a segment boundary
+    back: usize,
+    /// Option flags, see the `FLAG_*` constants
+    flags: u8,
+    /// Error for malformed UTF-8 following the text, not yet yielded
+    utf8_error: Option<GraphemeError>,
+    /// Item cached by [`peek`](Self::peek), along with the front offset before it was read
+    peeked: Option<(usize, Option<Result<Grapheme<N>>>)>,
+    /// Last backward search for an escape, as the offset searched before and the nearest
+    /// escape or non-ASCII byte found, if any
+    escape_hint: Option<(usize, Option<usize>)>,
+    /// Start and end of the escape sequence last scanned while searching backwards
+    sequence_hint: Option<(usize, usize)>,
 }
 
 impl<'a> GraphemeIterator<'a> {
     /// Creates a new grapheme cluster iterator.
     ///
+    /// Clusters are limited to [`MAX_GRAPHEME_SIZE`] code points, see
+    /// [`with_buffer_size`](Self::with_buffer_size) for longer clusters.
+    ///
     /// # Arguments
     ///
     /// * `text` - The input text to iterate over
@@ -92,57 +211,364 @@ impl<'a> GraphemeIterator<'a> {
     /// ```
     #[inline]
     pub fn new(text: &'a str, count_ansi: bool) -> Self {
+        Self::with_buffer_size(text, count_ansi)
+    }
+
+    /// Creates a new grapheme cluster iterator that splits oversized clusters.
+    ///
+    /// Identical to [`new`](Self::new), except clusters longer than [`MAX_GRAPHEME_SIZE`]
+    /// code points are split into several graphemes instead of producing a
+    /// `GraphemeError::BufferOverflow`. See [`lossy`](Self::lossy) for details.
+    ///
+    /// # Arguments
+    ///
+    /// * `text` - The input text to iterate over
+    /// * `count_ansi` - Whether to count ANSI escape sequences as graphemes
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use graphmemes::{GraphemeIterator, Result};
+    ///
+    /// # fn main() -> Result<()> {
+    /// let text = "a\u{0301}\u{0302}\u{0303}\u{0304}\u{0305}\u{0306}\u{0307}\u{0308}\u{0309}";
+    /// let graphemes: Vec<_> = GraphemeIterator::new_lossy(text, false).collect::<Result<_>>()?;
+    /// assert_eq!(graphemes.len(), 2);
+    /// assert_eq!(graphemes[0].len(), 8);
+    /// assert_eq!(graphemes[1].len(), 2);
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[inline]
+    pub fn new_lossy(text: &'a str, count_ansi: bool) -> Self {
+        Self::new(text, count_ansi).lossy()
+    }
+
+    /// Counts the grapheme clusters in a string without building them.
+    ///
+    /// Shorthand for [`try_count`](GraphemeIterator::try_count) on a new iterator, giving
+    /// the same count as `GraphemeIterator::new(text, count_ansi).count()` for input
+    /// without errors.
+    ///
+    /// # Arguments
+    ///
+    /// * `text` - The input text to count clusters in
+    /// * `count_ansi` - Whether to count ANSI escape sequences as graphemes
+    ///
+    /// # Returns
+    ///
+    /// The number of clusters, or the first error iteration would produce.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use graphmemes::{GraphemeIterator, Result};
+    ///
+    /// # fn main() -> Result<()> {
+    /// assert_eq!(GraphemeIterator::count_graphemes("Hello 👋🏽", false)?, 7);
+    /// assert_eq!(GraphemeIterator::count_graphemes("\x1b[31mred\x1b[0m", true)?, 5);
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[inline]
+    pub fn count_graphemes(text: &str, count_ansi: bool) -> Result<usize> {
+        GraphemeIterator::new(text, count_ansi).try_count()
+    }
+
+    /// Creates a new grapheme cluster iterator over UTF-8 encoded bytes.
+    ///
+    /// The bytes are validated when the iterator is created, without allocating. If they
+    /// contain malformed UTF-8, the clusters before the first malformed sequence are
+    /// yielded as usual, followed by a `GraphemeError::InvalidUtf8`, and iteration ends
+    /// there. Iterating from the back yields the error first.
+    ///
+    /// # Arguments
+    ///
+    /// * `bytes` - The UTF-8 input to iterate over
+    /// * `count_ansi` - Whether to count ANSI escape sequences as graphemes
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use graphmemes::{GraphemeError, GraphemeIterator};
+    ///
+    /// let mut iter = GraphemeIterator::from_utf8(b"a\xF0\x9F", false);
+    /// assert_eq!(iter.next().unwrap().unwrap().as_chars(), &['a']);
+    /// assert_eq!(iter.next().unwrap().unwrap_err(), GraphemeError::invalid_utf8(1, 2));
+    /// assert!(iter.next().is_none());
+    /// ```
+    pub fn from_utf8(bytes: &'a [u8], count_ansi: bool) -> Self {
+        let chunk = bytes.utf8_chunks().next();
+        let text = chunk.as_ref().map_or("", |chunk| chunk.valid());
+
+        let mut iter = Self::new(text, count_ansi);
+        if let Some(chunk) = chunk.filter(|chunk| !chunk.invalid().is_empty()) {
+            let len = chunk.invalid().len();
+            iter.utf8_error = Some(GraphemeError::invalid_utf8(text.len(), len));
+        }
+        iter
+    }
+}
+
+impl<'a, const N: usize> GraphemeIterator<'a, N> {
+    /// Creates a new grapheme cluster iterator with clusters of up to `N` code points.
+    ///
+    /// Clusters longer than `N` code points produce a `GraphemeError::BufferOverflow`.
+    /// `N` must be at least 1.
+    ///
+    /// # Arguments
+    ///
+    /// * `text` - The input text to iterate over
+    /// * `count_ansi` - Whether to count ANSI escape sequences as graphemes
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use graphmemes::GraphemeIterator;
+    ///
+    /// let text = "Z\u{0351}\u{0352}\u{0357}\u{0358}\u{035B}\u{035D}\u{035E}\u{0360}\u{0361}";
+    /// let iter = GraphemeIterator::<32>::with_buffer_size(text, false);
+    /// assert_eq!(iter.count(), 1);
+    /// ```
+    #[inline]
+    pub fn with_buffer_size(text: &'a str, count_ansi: bool) -> Self {
+        const { assert!(N > 0, "grapheme buffer must hold at least one code point") };
         Self {
-            chars: text.chars(),
-            position: 0,
-            buffer: ['\0'; MAX_GRAPHEME_SIZE],
-            buffer_len: 0,
-            state: STATE_START | ((count_ansi as u8) << 3),
-            prev_category: 0,
+            text,
+            front: 0,
+            back: text.len(),
+            flags: if count_ansi { FLAG_COUNT_ANSI } else { 0 },
+            utf8_error: None,
+            peeked: None,
+            escape_hint: None,
+            sequence_hint: None,
         }
     }
 
-    /// Returns whether ANSI sequences are being counted as graphemes.
+    /// Counts the remaining grapheme clusters without building them.
+    ///
+    /// Gives the same count as [`Iterator::count`] for input without errors, including
+    /// counted ANSI sequences and clusters split in lossy mode, but skips filling and
+    /// copying a [`Grapheme`] for every cluster.
+    ///
+    /// # Returns
+    ///
+    /// The number of clusters, or the first error iteration would produce.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use graphmemes::{GraphemeIterator, Result};
+    ///
+    /// # fn main() -> Result<()> {
+    /// let iter = GraphemeIterator::new_lossy("a\u{0301}\x1b[1mb", true);
+    /// assert_eq!(iter.try_count()?, 3);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn try_count(self) -> Result<usize> {
+        let mut count = 0;
+        let mut pos = self.front;
+
+        if let Some((_, Some(item))) = self.peeked {
+            item?;
+            count += 1;
+        }
+
+        while pos < self.back {
+            if self.text[pos..].starts_with(ESC) {
+                let (segment, end) = self.scan_ansi(pos);
+                match segment {
+                    Segment::Ansi if self.count_ansi() => count += 1,
+                    Segment::Invalid(err) => return Err(err),
+                    _ => {}
+                }
+                pos = end;
+            } else {
+                let (end, _, overflow) = self.scan_cluster(pos, |_, _| {});
+                if let Some(err) = overflow {
+                    return Err(err);
+                }
+                count += 1;
+                pos = end;
+            }
+        }
+
+        match self.utf8_error {
+            Some(err) => Err(err),
+            None => Ok(count),
+        }
+    }
+
+    /// Returns the next item without consuming it.
+    ///
+    /// The item is cached, so peeking repeatedly returns the same item without rescanning,
+    /// and the next call to [`next`](Iterator::next) yields it. Peeking doesn't affect
+    /// [`position`](Self::position) either.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use graphmemes::GraphemeIterator;
+    ///
+    /// let mut iter = GraphemeIterator::new("👋🏽!", false);
+    /// assert!(matches!(iter.peek(), Some(Ok(g)) if *g == "👋🏽"));
+    /// assert_eq!(iter.position(), 0);
+    /// assert!(matches!(iter.next(), Some(Ok(g)) if g == "👋🏽"));
+    /// assert_eq!(iter.position(), 8);
+    /// ```
+    pub fn peek(&mut self) -> Option<&Result<Grapheme<N>>> {
+        if self.peeked.is_none() {
+            let position = self.front;
+            let item = self.next();
+            self.peeked = Some((position, item));
+        }
+        self.peeked.as_ref().and_then(|(_, item)| item.as_ref())
+    }
+
+    /// Returns the byte offset in the text from which the next item will be read.
+    ///
+    /// Uncounted ANSI escape sequences are only skipped when the following item is read, so
+    /// the offset may lie before such a sequence rather than at the next item.
     #[inline]
-    fn count_ansi(&self) -> bool {
-        (self.state >> 3) & 1 == 1
+    pub fn position(&self) -> usize {
+        self.peeked
+            .as_ref()
+            .map_or(self.front, |&(position, _)| position)
+    }
+
+    /// Returns the part of the text not yet consumed by the iterator.
+    ///
+    /// Like [`Chars::as_str`](core::str::Chars::as_str), this is the text between the front
+    /// and back of the iterator, starting at [`position`](Self::position). An item cached by
+    /// [`peek`](Self::peek) hasn't been consumed, so its text is included, while the
+    /// malformed tail of the input of [`from_utf8`](Self::from_utf8) never is. The slice always
+    /// starts and ends on a cluster boundary.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use graphmemes::GraphemeIterator;
+    ///
+    /// let mut iter = GraphemeIterator::new("abc👋", false);
+    /// iter.next();
+    /// iter.next();
+    /// assert_eq!(iter.as_str(), "c👋");
+    /// iter.next_back();
+    /// assert_eq!(iter.as_str(), "c");
+    /// ```
+    #[inline]
+    pub fn as_str(&self) -> &'a str {
+        &self.text[self.position()..self.back]
+    }
+
+    /// Pairs each item with the byte offset of the cluster it was read from.
+    ///
+    /// The offset is the start of the cluster in the text, the same as
+    /// [`Grapheme::byte_offset`], for callers that would rather keep it alongside the cluster.
+    /// Uncounted ANSI escape sequences are skipped as usual, so the offset of the cluster after
+    /// one lies past the sequence. Errors are passed through unchanged.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use graphmemes::{GraphemeIterator, Result};
+    ///
+    /// # fn main() -> Result<()> {
+    /// let offsets: Vec<_> = GraphemeIterator::new("\x1b[1ma👋", false)
+    ///     .with_position()
+    ///     .map(|item| item.map(|(offset, _)| offset))
+    ///     .collect::<Result<_>>()?;
+    /// assert_eq!(offsets, [4, 5]);
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[inline]
+    pub fn with_position(
+        self,
+    ) -> impl DoubleEndedIterator<Item = Result<(usize, Grapheme<N>)>> + 'a {
+        self.map(|item| item.map(|grapheme| (grapheme.byte_offset(), grapheme)))
     }
 
-    /// Returns the current processing state.
+    /// Switches the iterator to lossy mode.
+    ///
+    /// When a cluster exceeds the buffer size, the characters that fit are yielded as one
+    /// grapheme and a fresh cluster is started from the character that didn't fit, instead
+    /// of producing a `GraphemeError::BufferOverflow`. Clusters that fit are unaffected.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use graphmemes::GraphemeIterator;
+    ///
+    /// let text = "a\u{0301}\u{0302}\u{0303}\u{0304}";
+    /// let iter = GraphemeIterator::<2>::with_buffer_size(text, false).lossy();
+    /// assert_eq!(iter.count(), 3);
+    /// ```
     #[inline]
-    fn state(&self) -> u8 {
-        self.state & STATE_MASK
+    pub fn lossy(mut self) -> Self {
+        self.flags |= FLAG_LOSSY;
+        self
     }
 
-    /// Sets a new processing state while preserving ANSI counting flag.
+    /// Returns whether ANSI sequences are being counted as graphemes.
+    #[inline]
+    fn count_ansi(&self) -> bool {
+        self.flags & FLAG_COUNT_ANSI != 0
+    }
+
+    /// Returns whether oversized clusters are split instead of producing an error.
     #[inline]
-    fn set_state(&mut self, new_state: u8) {
-        self.state = (self.state & !STATE_MASK) | new_state;
+    fn is_lossy(&self) -> bool {
+        self.flags & FLAG_LOSSY != 0
     }
 
-    /// Determines if a character boundary exists before the given character.
+    /// Determines if a grapheme cluster boundary exists before the given character.
     ///
-    /// Implements UAX #29 grapheme cluster boundary rules.
+    /// Implements UAX #29 grapheme cluster boundary rules for a pair of adjacent
+    /// characters, where `prev_category` is the category of the preceding character and
+    /// `context` describes the cluster up to and including it.
     #[inline]
-    fn is_boundary(&mut self, c: char) -> bool {
-        let category = Grapheme::char_category(c);
+    fn is_boundary(prev_category: u32, category: u32, c: char, context: &ClusterContext) -> bool {
+        match (prev_category, category) {
+            // CR LF stays together (GB3)
+            (boundary::CR, boundary::LF) => false,
 
-        // Special case: first character is never a boundary
-        if self.buffer_len <= 1 {
-            self.prev_category = category;
-            return false;
-        }
+            // Always break after and before line breaks and controls (GB4, GB5)
+            (boundary::CR | boundary::LF | boundary::CONTROL, _)
+            | (_, boundary::CR | boundary::LF | boundary::CONTROL) => true,
+
+            // ASCII always starts a new cluster
+            _ if c.is_ascii() => true,
+
+            // Hangul syllable sequences (GB6, GB7, GB8)
+            (
+                boundary::HANGUL_L,
+                boundary::HANGUL_L
+                | boundary::HANGUL_V
+                | boundary::HANGUL_LV
+                | boundary::HANGUL_LVT,
+            ) => false,
+            (boundary::HANGUL_LV | boundary::HANGUL_V, boundary::HANGUL_V | boundary::HANGUL_T) => {
+                false
+            }
+            (boundary::HANGUL_LVT | boundary::HANGUL_T, boundary::HANGUL_T) => false,
 
-        let is_boundary = match (self.prev_category, category) {
-            // ZWJ sequences
+            // ZWJ sequences (GB9, GB11)
             (_, boundary::ZWJ) => false,
-            (boundary::ZWJ, _) if is_emoji(c) => false,
+            (boundary::ZWJ, boundary::PICTOGRAPHIC) if context.pictographic_zwj => false,
 
             // Extend characters never form boundary
-            (_, boundary::EXTEND) => false,
+            (_, boundary::EXTEND | boundary::INCB_LINKER) => false,
+
+            // Indic consonants linked into a conjunct (GB9c)
+            (
+                boundary::EXTEND | boundary::ZWJ | boundary::EMOJI_MOD | boundary::INCB_LINKER,
+                boundary::INCB_CONSONANT,
+            ) if context.indic_linked => false,
 
-            // Regional indicators must pair
-            (boundary::REGIONAL, boundary::REGIONAL) => false,
+            // Regional indicators pair up two at a time (GB12, GB13)
+            (boundary::REGIONAL, boundary::REGIONAL) if context.regional_odd => false,
 
             // Emoji modifiers don't form boundary
             (_, boundary::EMOJI_MOD) => false,
@@ -155,175 +581,494 @@ impl<'a> GraphemeIterator<'a> {
 
             // Everything else is a boundary
             _ => true,
-        };
-
-        // Update category state
-        self.prev_category = category;
-        is_boundary
+        }
     }
 
-    /// Processes a single character, potentially producing a complete grapheme.
-    ///
-    /// This function implements a state machine that handles:
-    /// - Fast path for standalone ASCII characters
-    /// - ANSI escape sequences
-    /// - Complex grapheme clusters
-    ///
-    /// The state transitions are:
-    /// ```text
-    /// STATE_START     -> STATE_IN_GRAPHEME  (on char accumulation)
-    ///                 -> STATE_IN_ANSI      (on ANSI escape)
-    /// STATE_IN_ANSI   -> STATE_START        (on ANSI terminator)
-    /// STATE_IN_GRAPHEME -> STATE_IN_GRAPHEME (continuing cluster)
-    ///                   -> STATE_START      (on boundary)
-    /// ```
+    /// Scans the segment starting at byte offset `start`.
     ///
-    /// The ASCII fast path optimizes single-character graphemes while maintaining
-    /// proper boundary detection for sequences.
-    ///
-    /// # Arguments
-    ///
-    /// * `c` - The character to process
+    /// `start` must lie on a segment boundary. As the back of the unconsumed input is also a
+    /// segment boundary, the segment found never extends past it. Escape sequences are
+    /// still scanned against the whole text, so a sequence cut short just before the back
+    /// is classified the same way in both directions.
     ///
     /// # Returns
     ///
-    /// * `Ok(Some(grapheme))` - A complete grapheme was formed
-    /// * `Ok(None)` - Character was processed but no complete grapheme yet
-    /// * `Err(error)` - An error occurred during processing:
-    ///   - `GraphemeError::BufferOverflow` if cluster exceeds `MAX_GRAPHEME_SIZE`
-    ///   - `GraphemeError::InvalidAnsiSequence` for malformed ANSI sequences
+    /// The segment found and the byte offset just past it. The offset is always greater
+    /// than `start`, so repeated scanning always makes progress.
     #[inline]
-    fn process_char(&mut self, c: char) -> Result<Option<Grapheme>> {
-        let current_pos = self.position;
-        self.position += c.len_utf8();
-
-        match (c, self.state()) {
-            // ASCII fast path - but only for definite boundaries
-            (c, STATE_START) if c.is_ascii() && c != '\x1b' => {
-                // Handle buffer state
-                if self.buffer_len >= MAX_GRAPHEME_SIZE {
-                    return Err(GraphemeError::buffer_overflow(current_pos, c.len_utf8()));
-                }
+    fn scan_segment(&self, start: usize) -> (Segment<N>, usize) {
+        if self.text[start..].starts_with(ESC) {
+            self.scan_ansi(start)
+        } else {
+            self.scan_grapheme(start)
+        }
+    }
 
-                self.buffer[self.buffer_len] = c;
-                self.buffer_len += 1;
-
-                // Continue with normal boundary detection
-                if self.buffer_len == 1 {
-                    self.set_state(STATE_IN_GRAPHEME);
-                    Ok(None)
-                } else {
-                    let grapheme = Grapheme::new(self.buffer, self.buffer_len - 1);
-                    self.buffer[0] = self.buffer[self.buffer_len - 1];
-                    self.buffer_len = 1;
-                    self.set_state(STATE_IN_GRAPHEME);
-                    Ok(Some(grapheme))
-                }
-            }
-            ('\x1b', _) => {
-                if self.buffer_len > 0 {
-                    let grapheme = Grapheme::new(self.buffer, self.buffer_len);
-                    self.buffer_len = 0;
-                    self.set_state(STATE_IN_ANSI);
-                    Ok(Some(grapheme))
-                } else {
-                    self.set_state(STATE_IN_ANSI);
-                    Ok(None)
-                }
-            }
-            (c, STATE_IN_ANSI) => {
-                if c.is_ascii_alphabetic() {
-                    self.set_state(STATE_START);
-                    if self.count_ansi() {
-                        self.buffer[0] = '\x1b';
-                        Ok(Some(Grapheme::new(self.buffer, 1)))
-                    } else {
-                        Ok(None)
-                    }
-                } else if !c.is_ascii() {
-                    Err(GraphemeError::invalid_ansi(current_pos, c.len_utf8()))
-                } else {
-                    Ok(None)
+    /// Scans an ANSI escape sequence starting at the escape character at `start`.
+    ///
+    /// CSI (`ESC [`) and OSC (`ESC ]`) sequences are handed off to
+    /// [`scan_csi`](Self::scan_csi) and [`scan_osc`](Self::scan_osc). Other sequences are
+    /// terminated by an ASCII alphabetic character. Another escape character abandons the
+    /// current sequence, and non-ASCII characters are invalid. An invalid character is left
+    /// unconsumed so it can be processed as text.
+    #[inline]
+    fn scan_ansi(&self, start: usize) -> (Segment<N>, usize) {
+        let body = start + ESC.len_utf8();
+        match self.text.as_bytes().get(body) {
+            Some(b'[') => return self.scan_csi(body + 1),
+            Some(b']') => return self.scan_osc(body + 1),
+            _ => {}
+        }
+
+        for (i, c) in self.text[body..].char_indices() {
+            let pos = body + i;
+            match c {
+                ESC => return (Segment::Unterminated, pos),
+                c if c.is_ascii_alphabetic() => return (Segment::Ansi, pos + 1),
+                c if !c.is_ascii() => {
+                    let err = GraphemeError::invalid_ansi(pos, c.len_utf8());
+                    return (Segment::Invalid(err), pos);
                 }
+                _ => {}
             }
-            (c, _) => {
-                if self.buffer_len >= MAX_GRAPHEME_SIZE {
-                    return Err(GraphemeError::buffer_overflow(current_pos, c.len_utf8()));
-                }
+        }
+
+        (Segment::Unterminated, self.text.len())
+    }
 
-                self.buffer[self.buffer_len] = c;
-                self.buffer_len += 1;
-
-                if self.buffer_len == 1 {
-                    self.set_state(STATE_IN_GRAPHEME);
-                    Ok(None)
-                } else if c.is_ascii() || self.is_boundary(c) {
-                    let grapheme = Grapheme::new(self.buffer, self.buffer_len - 1);
-                    // Move the last character to the start of the buffer
-                    self.buffer[0] = self.buffer[self.buffer_len - 1];
-                    self.buffer_len = 1;
-                    self.set_state(STATE_IN_GRAPHEME);
-                    Ok(Some(grapheme))
-                } else {
-                    self.set_state(STATE_IN_GRAPHEME);
-                    Ok(None)
+    /// Scans the body of a CSI sequence starting just after the `ESC [` introducer.
+    ///
+    /// Follows the ECMA-48 grammar: any number of parameter bytes (`0x30–0x3F`), then any
+    /// number of intermediate bytes (`0x20–0x2F`), then a final byte (`0x40–0x7E`). Any
+    /// other ASCII byte, including a parameter byte after an intermediate byte, makes the
+    /// sequence invalid and is consumed with it. Escape and non-ASCII characters are
+    /// handled as in [`scan_ansi`](Self::scan_ansi).
+    #[inline]
+    fn scan_csi(&self, start: usize) -> (Segment<N>, usize) {
+        let mut intermediate = false;
+
+        for (i, c) in self.text[start..].char_indices() {
+            let pos = start + i;
+            match c {
+                '\x40'..='\x7e' => return (Segment::Ansi, pos + 1),
+                '\x30'..='\x3f' if !intermediate => {}
+                '\x20'..='\x2f' => intermediate = true,
+                ESC => return (Segment::Unterminated, pos),
+                c => {
+                    let err = GraphemeError::invalid_ansi(pos, c.len_utf8());
+                    let end = if c.is_ascii() { pos + 1 } else { pos };
+                    return (Segment::Invalid(err), end);
                 }
             }
         }
+
+        (Segment::Unterminated, self.text.len())
     }
-}
 
-impl<'a> Iterator for GraphemeIterator<'a> {
-    type Item = Result<Grapheme>;
+    /// Scans the payload of an OSC sequence starting just after the `ESC ]` introducer.
+    ///
+    /// The payload is terminated by BEL or by the string terminator `ESC \`, and may
+    /// contain any other ASCII character. An escape character not followed by `\`
+    /// abandons the sequence, and non-ASCII characters are invalid as in
+    /// [`scan_ansi`](Self::scan_ansi).
+    #[inline]
+    fn scan_osc(&self, start: usize) -> (Segment<N>, usize) {
+        let mut chars = self.text[start..].char_indices().peekable();
 
-    fn next(&mut self) -> Option<Self::Item> {
-        while let Some(c) = self.chars.next() {
-            match self.process_char(c) {
-                Ok(Some(grapheme)) => return Some(Ok(grapheme)),
-                Ok(None) => continue,
-                Err(e) => return Some(Err(e)),
+        while let Some((i, c)) = chars.next() {
+            let pos = start + i;
+            match c {
+                BEL => return (Segment::Ansi, pos + 1),
+                ESC if matches!(chars.peek(), Some((_, '\\'))) => return (Segment::Ansi, pos + 2),
+                ESC => return (Segment::Unterminated, pos),
+                c if !c.is_ascii() => {
+                    let err = GraphemeError::invalid_ansi(pos, c.len_utf8());
+                    return (Segment::Invalid(err), pos);
+                }
+                _ => {}
             }
         }
 
-        // Handle remaining buffer
-        if self.buffer_len > 0 {
-            let grapheme = Grapheme::new(self.buffer, self.buffer_len);
-            self.buffer_len = 0;
-            Some(Ok(grapheme))
-        } else {
-            None
+        (Segment::Unterminated, self.text.len())
+    }
+
+    /// Scans the grapheme cluster starting at `start`.
+    ///
+    /// See [`scan_cluster`](Self::scan_cluster) for how the cluster's extent is found.
+    #[inline]
+    fn scan_grapheme(&self, start: usize) -> (Segment<N>, usize) {
+        let mut buffer = ['\0'; N];
+        let (end, len, overflow) = self.scan_cluster(start, |i, c| buffer[i] = c);
+
+        match overflow {
+            Some(err) => (Segment::Invalid(err), end),
+            None => {
+                let grapheme = Grapheme::with_span(buffer, len, start, end - start);
+                (Segment::Grapheme(grapheme), end)
+            }
         }
     }
-}
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use heapless::Vec;
+    /// Finds the extent of the grapheme cluster starting at `start`.
+    ///
+    /// Characters are accumulated until a boundary is found, and each one that fits in the
+    /// buffer is passed to `push` along with its index in the cluster. If the cluster
+    /// exceeds the buffer size `N`, the rest of the cluster is skipped and a
+    /// `GraphemeError::BufferOverflow` is returned for the first character that didn't fit.
+    /// In lossy mode, the cluster is cut short before that character instead.
+    ///
+    /// # Returns
+    ///
+    /// The byte offset just past the cluster, the number of characters passed to `push`
+    /// and the overflow error, if any.
+    #[inline(always)]
+    fn scan_cluster(
+        &self,
+        start: usize,
+        mut push: impl FnMut(usize, char),
+    ) -> (usize, usize, Option<GraphemeError>) {
+        let mut len = 0;
+        let mut prev_category = 0;
+        let mut context = ClusterContext::default();
+        let mut overflow = None;
 
-    /// Maximum number of graphemes we'll test at once
-    const TEST_VEC_SIZE: usize = 32;
+        for (i, c) in self.text[start..self.back].char_indices() {
+            let pos = start + i;
+            let category = Grapheme::char_category(c);
 
-    /// Helper to collect graphemes and check results
-    fn collect_graphemes(input: &str, count_ansi: bool) -> Result<Vec<Grapheme, TEST_VEC_SIZE>> {
-        let mut vec = Vec::new();
-        let iter = GraphemeIterator::new(input, count_ansi);
-        for result in iter {
-            vec.extend_from_slice(&[result?])
-                .map_err(|_| GraphemeError::buffer_overflow(0, 0))?;
+            if len > 0 && (c == ESC || Self::is_boundary(prev_category, category, c, &context)) {
+                return (pos, len, overflow);
+            }
+
+            if len < N {
+                push(len, c);
+                len += 1;
+            } else if self.is_lossy() {
+                return (pos, len, overflow);
+            } else if overflow.is_none() {
+                overflow = Some(GraphemeError::buffer_overflow(pos, c.len_utf8()));
+            }
+            prev_category = category;
+            context.advance(category);
         }
-        Ok(vec)
-    }
 
-    #[test]
-    fn test_ascii() {
-        let text = "abc";
-        let graphemes = collect_graphemes(text, false).unwrap();
+        (self.back, len, overflow)
+    }
 
-        assert_eq!(graphemes.len(), 3);
-        assert_eq!(graphemes[0].as_chars(), &['a']);
-        assert_eq!(graphemes[1].as_chars(), &['b']);
-        assert_eq!(graphemes[2].as_chars(), &['c']);
+    /// Finds the nearest escape character before `pos` in the same run of ASCII.
+    ///
+    /// Reverse iteration asks for this at every ASCII character it walks back over, so the
+    /// last result is kept and reused while `pos` stays above it. Each byte of a long run of
+    /// ASCII is then examined once in total, rather than once per character.
+    #[inline]
+    fn escape_before(&mut self, pos: usize) -> Option<usize> {
+        let stop = match self.escape_hint {
+            // Nothing between the byte found and the last search offset stops the search
+            Some((searched, stop)) if pos <= searched && stop.is_none_or(|stop| stop < pos) => stop,
+            _ => {
+                let bytes = &self.text.as_bytes()[self.front..pos];
+                let stop = bytes
+                    .iter()
+                    .rposition(|&b| b == 0x1b || !b.is_ascii())
+                    .map(|i| self.front + i);
+                self.escape_hint = Some((pos, stop));
+                stop
+            }
+        };
+        stop.filter(|&stop| stop >= self.front && self.text.as_bytes()[stop] == 0x1b)
+    }
+
+    /// Returns the end of the escape sequence starting at `start`, as found by
+    /// [`scan_ansi`](Self::scan_ansi).
+    ///
+    /// Like [`escape_before`](Self::escape_before), the last result is kept, since walking
+    /// back through a long unterminated sequence asks for the same escape repeatedly.
+    #[inline]
+    fn sequence_end(&mut self, start: usize) -> usize {
+        match self.sequence_hint {
+            Some((hint, end)) if hint == start => end,
+            _ => {
+                let end = self.scan_ansi(start).1;
+                self.sequence_hint = Some((start, end));
+                end
+            }
+        }
+    }
+
+    /// Finds the start of the segment beginning with or containing the escape at `pos`.
+    ///
+    /// Every escape character starts a new segment, except the one in a string terminator
+    /// (`ESC \`) that ends an OSC sequence.
+    #[inline]
+    fn escape_segment_start(&mut self, pos: usize) -> usize {
+        if self.text.as_bytes().get(pos + 1) == Some(&b'\\') {
+            if let Some(osc) = self.escape_before(pos) {
+                if self.text[osc..].starts_with("\x1b]") && self.sequence_end(osc) == pos + 2 {
+                    return osc;
+                }
+            }
+        }
+        pos
+    }
+
+    /// Finds the escape introducing a CSI sequence whose last byte is at `end - 1`.
+    ///
+    /// Walks the CSI grammar in reverse over the intermediate and parameter bytes, so only
+    /// the bytes that could belong to the sequence are examined.
+    #[inline]
+    fn csi_escape_before(&self, end: usize) -> Option<usize> {
+        const PARAMETER: core::ops::RangeInclusive<u8> = 0x30..=0x3f;
+        const INTERMEDIATE: core::ops::RangeInclusive<u8> = 0x20..=0x2f;
+
+        let (last, body) = self.text.as_bytes()[self.front..end].split_last()?;
+        if INTERMEDIATE.contains(last) {
+            return None;
+        }
+
+        let mut i = body.len();
+        while i > 0 && INTERMEDIATE.contains(&body[i - 1]) {
+            i -= 1;
+        }
+        // A parameter byte only ends a sequence when it follows an intermediate byte
+        if PARAMETER.contains(last) && i == body.len() {
+            return None;
+        }
+        while i > 0 && PARAMETER.contains(&body[i - 1]) {
+            i -= 1;
+        }
+
+        (i >= 2 && body[i - 2..i] == *b"\x1b[").then(|| self.front + i - 2)
+    }
+
+    /// Finds the start of the ANSI escape sequence ending exactly at `end`, if any.
+    ///
+    /// Besides terminated sequences, this finds sequences cut short at `end` by another
+    /// escape, an invalid character or the end of input. Sequences can only end on an
+    /// ASCII character, and apart from OSC payloads the bytes before the last one are
+    /// tightly constrained, so only OSC sequences and cut-off sequences need a longer
+    /// search.
+    fn sequence_ending_at(&mut self, end: usize) -> Option<usize> {
+        let bytes = self.text.as_bytes();
+        let last = bytes[..end].last().copied().filter(u8::is_ascii)?;
+        let cut = bytes.get(end).is_none_or(|&b| b == 0x1b || !b.is_ascii());
+
+        let escape = if cut || last == BEL as u8 || last == b'\\' {
+            self.escape_before(end)?
+        } else if last.is_ascii_alphabetic() {
+            let body = &bytes[self.front..end - 1];
+            let i = body
+                .iter()
+                .rposition(|&b| b == 0x1b || !b.is_ascii() || b.is_ascii_alphabetic())?;
+            (body[i] == 0x1b).then_some(self.front + i)?
+        } else {
+            self.csi_escape_before(end)?
+        };
+
+        let start = self.escape_segment_start(escape);
+        (self.sequence_end(start) == end).then_some(start)
+    }
+
+    /// Finds a segment boundary at or before the last character before `end`.
+    ///
+    /// The returned offset is one where forward iteration from the front is guaranteed to
+    /// start a new segment, regardless of what precedes it. Scanning forward from it to
+    /// `end` therefore produces the same segments forward iteration would.
+    fn rewind_point(&mut self, end: usize) -> usize {
+        let text = self.text;
+        let mut chars = text[self.front..end].char_indices().rev().peekable();
+
+        while let Some((i, c)) = chars.next() {
+            let pos = self.front + i;
+            let Some(&(_, prev)) = chars.peek() else {
+                break;
+            };
+
+            if c.is_ascii() {
+                // ASCII always starts a new cluster unless it's part of an escape sequence.
+                // Walking back only reaches ASCII as the last character before `end`, just
+                // before a non-ASCII character or as the CR of a CR LF pair, so any sequence
+                // containing it ends right after it.
+                if let Some(start) = self.sequence_ending_at(pos + 1) {
+                    return start;
+                }
+                if !(prev == '\r' && c == '\n') {
+                    return pos;
+                }
+            } else {
+                let prev_category = Grapheme::char_category(prev);
+                let category = Grapheme::char_category(c);
+                if Self::is_boundary(prev_category, category, c, &ClusterContext::JOINING) {
+                    return pos;
+                }
+            }
+        }
+
+        self.front
+    }
+
+    /// Converts a scanned segment spanning `start..end` into an iterator item.
+    ///
+    /// Returns `None` for segments that are skipped, such as ANSI escape sequences when
+    /// they aren't being counted.
+    #[inline]
+    fn emit(&self, segment: Segment<N>, start: usize, end: usize) -> Option<Result<Grapheme<N>>> {
+        match segment {
+            Segment::Grapheme(grapheme) => Some(Ok(grapheme)),
+            Segment::Ansi if self.count_ansi() => {
+                let mut chars = ['\0'; N];
+                chars[0] = ESC;
+                Some(Ok(Grapheme::with_span(chars, 1, start, end - start)))
+            }
+            Segment::Ansi | Segment::Unterminated => None,
+            Segment::Invalid(err) => Some(Err(err)),
+        }
+    }
+}
+
+impl<'a, const N: usize> Iterator for GraphemeIterator<'a, N> {
+    type Item = Result<Grapheme<N>>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if let Some((_, item)) = self.peeked.take() {
+            return item;
+        }
+
+        while self.front < self.back {
+            let start = self.front;
+            let (segment, end) = self.scan_segment(start);
+            self.front = end;
+
+            if let Some(item) = self.emit(segment, start, end) {
+                return Some(item);
+            }
+        }
+
+        self.utf8_error.take().map(Err)
+    }
+
+    /// Skips `n` items without building a [`Grapheme`] for each, then yields the next one.
+    ///
+    /// Behaves exactly like calling [`next`](Iterator::next) `n + 1` times, so errors and
+    /// counted ANSI sequences are skipped as items too.
+    fn nth(&mut self, mut n: usize) -> Option<Self::Item> {
+        if let Some((_, item)) = self.peeked.take() {
+            if n == 0 || item.is_none() {
+                return item;
+            }
+            n -= 1;
+        }
+
+        while n > 0 && self.front < self.back {
+            let start = self.front;
+            if self.text[start..].starts_with(ESC) {
+                let (segment, end) = self.scan_ansi(start);
+                match segment {
+                    Segment::Ansi if self.count_ansi() => n -= 1,
+                    Segment::Invalid(_) => n -= 1,
+                    _ => {}
+                }
+                self.front = end;
+            } else {
+                let (end, _, _) = self.scan_cluster(start, |_, _| {});
+                n -= 1;
+                self.front = end;
+            }
+        }
+
+        if n > 0 {
+            // The trailing UTF-8 error is skipped as the last item, if there is one
+            self.utf8_error = None;
+            return None;
+        }
+        self.next()
+    }
+}
+
+impl<'a, const N: usize> DoubleEndedIterator for GraphemeIterator<'a, N> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        if let Some(err) = self.utf8_error.take() {
+            return Some(Err(err));
+        }
+
+        while self.front < self.back {
+            // Rescan forward from a known boundary to find where the last segment starts
+            let mut start = self.rewind_point(self.back);
+            let (segment, end) = loop {
+                let (segment, end) = self.scan_segment(start);
+                if end >= self.back {
+                    break (segment, end);
+                }
+                start = end;
+            };
+            self.back = start;
+
+            if let Some(item) = self.emit(segment, start, end) {
+                return Some(item);
+            }
+        }
+
+        // The peeked item is all that's left
+        self.peeked.take().and_then(|(_, item)| item)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::WidthConfig;
+    use heapless::Vec;
+
+    /// Maximum number of graphemes we'll test at once
+    const TEST_VEC_SIZE: usize = 32;
+
+    /// Helper to collect graphemes and check results
+    fn collect_graphemes(input: &str, count_ansi: bool) -> Result<Vec<Grapheme, TEST_VEC_SIZE>> {
+        let mut vec = Vec::new();
+        let iter = GraphemeIterator::new(input, count_ansi);
+        for result in iter {
+            vec.extend_from_slice(&[result?])
+                .map_err(|_| GraphemeError::buffer_overflow(0, 0))?;
+        }
+        Ok(vec)
+    }
+
+    /// Helper to collect graphemes from the back of the input
+    fn collect_graphemes_rev(
+        input: &str,
+        count_ansi: bool,
+    ) -> Result<Vec<Grapheme, TEST_VEC_SIZE>> {
+        let mut vec = Vec::new();
+        let iter = GraphemeIterator::new(input, count_ansi).rev();
+        for result in iter {
+            vec.extend_from_slice(&[result?])
+                .map_err(|_| GraphemeError::buffer_overflow(0, 0))?;
+        }
+        Ok(vec)
+    }
+
+    /// Asserts that reverse iteration yields the forward clusters in reverse order
+    fn assert_rev_matches(input: &str, count_ansi: bool) {
+        let forward = collect_graphemes(input, count_ansi).unwrap();
+        let reverse = collect_graphemes_rev(input, count_ansi).unwrap();
+        assert_eq!(
+            forward.len(),
+            reverse.len(),
+            "cluster count differs for {input:?}"
+        );
+        for (f, r) in forward.iter().zip(reverse.iter().rev()) {
+            assert_eq!(f.as_chars(), r.as_chars(), "cluster differs for {input:?}");
+        }
+    }
+
+    #[test]
+    fn test_ascii() {
+        let text = "abc";
+        let graphemes = collect_graphemes(text, false).unwrap();
+
+        assert_eq!(graphemes.len(), 3);
+        assert_eq!(graphemes[0].as_chars(), &['a']);
+        assert_eq!(graphemes[1].as_chars(), &['b']);
+        assert_eq!(graphemes[2].as_chars(), &['c']);
     }
 
     #[test]
@@ -355,6 +1100,22 @@ mod tests {
         assert_eq!(graphemes[0].as_chars(), &['e', '\u{0301}']);
     }
 
+    #[test]
+    fn test_variation_selectors() {
+        let inputs = [
+            "a\u{FE00}",        // Standardized variation sequence
+            "\u{231A}\u{FE0E}", // Text presentation selector
+            "字\u{E0100}",      // Ideographic variation sequence
+            "\u{1820}\u{180B}", // Mongolian free variation selector
+        ];
+        for input in inputs {
+            let graphemes = collect_graphemes(input, false).unwrap();
+            assert_eq!(graphemes.len(), 1, "{input:?}");
+            assert_eq!(graphemes[0].len(), 2, "{input:?}");
+            assert_rev_matches(input, false);
+        }
+    }
+
     #[test]
     fn test_zwj_sequences() {
         // More comprehensive ZWJ tests
@@ -367,6 +1128,218 @@ mod tests {
         assert_eq!(graphemes.len(), 1);
     }
 
+    #[test]
+    fn test_regional_indicators() {
+        // (input, expected cluster lengths)
+        let cases: [(&str, &[usize]); 4] = [
+            ("🇺🇸", &[2]),
+            ("🇺🇸🇬", &[2, 1]),
+            ("🇺🇸🇬🇧", &[2, 2]),
+            ("a🇺🇸🇬🇧🇫", &[1, 2, 2, 1]),
+        ];
+        for (input, lengths) in cases {
+            let graphemes = collect_graphemes(input, false).unwrap();
+            let actual: Vec<usize, TEST_VEC_SIZE> = graphemes.iter().map(|g| g.len()).collect();
+            assert_eq!(actual, lengths, "{input:?}");
+            assert_rev_matches(input, false);
+        }
+
+        // A mark between indicators starts a new run
+        let graphemes = collect_graphemes("🇺\u{0301}🇸🇬", false).unwrap();
+        assert_eq!(graphemes.len(), 2);
+        assert_eq!(graphemes[1].as_chars(), &['🇸', '🇬']);
+    }
+
+    #[test]
+    fn test_indic_conjuncts() {
+        // (input, expected cluster lengths)
+        let cases: [(&str, &[usize]); 9] = [
+            ("क्षि", &[4]),        // Devanagari ksha with vowel sign i
+            ("क्", &[2]),          // Consonant and virama
+            ("कष", &[1, 1]),      // Unlinked consonants
+            ("क\u{093C}्ष", &[4]), // Nukta between consonant and virama
+            ("क्\u{200D}ष", &[4]), // ZWJ after the virama
+            ("अ्क", &[2, 1]),      // Virama after a vowel doesn't link
+            ("स्त्र", &[5]),        // Three consonant conjunct
+            ("ক্ষ", &[3]),         // Bengali ksha
+            ("ക്ക", &[3]),         // Malayalam kka
+        ];
+        for (input, lengths) in cases {
+            let graphemes = collect_graphemes(input, false).unwrap();
+            let actual: Vec<usize, TEST_VEC_SIZE> = graphemes.iter().map(|g| g.len()).collect();
+            assert_eq!(actual, lengths, "{input:?}");
+            assert_rev_matches(input, false);
+        }
+
+        let graphemes = collect_graphemes("नमस्ते", false).unwrap();
+        assert_eq!(graphemes.len(), 3); // "न" "म" "स्ते"
+    }
+
+    #[test]
+    fn test_crlf() {
+        let graphemes = collect_graphemes("a\r\nb", false).unwrap();
+        assert_eq!(graphemes.len(), 3, "CRLF should be one grapheme");
+        assert_eq!(graphemes[0].as_chars(), &['a']);
+        assert_eq!(graphemes[1].as_chars(), &['\r', '\n']);
+        assert_eq!(graphemes[2].as_chars(), &['b']);
+
+        let graphemes = collect_graphemes("\n\r", false).unwrap();
+        assert_eq!(graphemes.len(), 2, "LF CR should not be joined");
+        assert_eq!(graphemes[0].as_chars(), &['\n']);
+        assert_eq!(graphemes[1].as_chars(), &['\r']);
+    }
+
+    #[test]
+    fn test_controls() {
+        let graphemes = collect_graphemes("a\tb\x07c", false).unwrap();
+        assert_eq!(graphemes.len(), 5);
+        assert_eq!(graphemes[1].as_chars(), &['\t']);
+        assert_eq!(graphemes[3].as_chars(), &['\x07']);
+
+        // Controls never merge with a following combining mark
+        for input in [
+            "\x07\u{0301}",
+            "\t\u{0301}",
+            "\u{0085}\u{0301}",
+            "\u{2028}\u{200D}",
+        ] {
+            let graphemes = collect_graphemes(input, false).unwrap();
+            assert_eq!(graphemes.len(), 2, "{input:?}");
+            assert_rev_matches(input, false);
+        }
+
+        // Nor with a preceding prepend character
+        let graphemes = collect_graphemes("\u{0600}\x00", false).unwrap();
+        assert_eq!(graphemes.len(), 2);
+    }
+
+    #[test]
+    fn test_hangul_jamo() {
+        // 각 as decomposed L + V + T jamo
+        let text = "\u{1100}\u{1161}\u{11A8}";
+        let graphemes = collect_graphemes(text, false).unwrap();
+        assert_eq!(graphemes.len(), 1, "L V T jamo should form one syllable");
+        assert_eq!(
+            graphemes[0].as_chars(),
+            &['\u{1100}', '\u{1161}', '\u{11A8}']
+        );
+
+        // Two decomposed syllables
+        let text = "\u{1112}\u{1161}\u{11AB}\u{1100}\u{1173}\u{11AF}"; // 한글
+        let graphemes = collect_graphemes(text, false).unwrap();
+        assert_eq!(graphemes.len(), 2);
+    }
+
+    #[test]
+    fn test_hangul_syllables() {
+        // 가 (LV) followed by a trailing consonant jamo
+        let text = "\u{AC00}\u{11A8}";
+        let graphemes = collect_graphemes(text, false).unwrap();
+        assert_eq!(graphemes.len(), 1, "LV syllable should absorb trailing T");
+
+        // 각 (LVT) followed by a trailing consonant jamo, then a new syllable
+        let text = "\u{AC01}\u{11A8}\u{AC00}";
+        let graphemes = collect_graphemes(text, false).unwrap();
+        assert_eq!(graphemes.len(), 2);
+        assert_eq!(graphemes[0].as_chars(), &['\u{AC01}', '\u{11A8}']);
+
+        // Precomposed syllables never join each other
+        let graphemes = collect_graphemes("한국어", false).unwrap();
+        assert_eq!(graphemes.len(), 3);
+    }
+
+    #[test]
+    fn test_byte_spans() {
+        let graphemes = collect_graphemes("a👋b", false).unwrap();
+        let offsets: Vec<usize, 3> = graphemes.iter().map(|g| g.byte_offset()).collect();
+        let lens: Vec<usize, 3> = graphemes.iter().map(|g| g.byte_len()).collect();
+        assert_eq!(offsets, [0, 1, 5]);
+        assert_eq!(lens, [1, 4, 1]);
+
+        // Reverse iteration reports the same spans
+        let graphemes = collect_graphemes_rev("a👋b", false).unwrap();
+        let offsets: Vec<usize, 3> = graphemes.iter().map(|g| g.byte_offset()).collect();
+        assert_eq!(offsets, [5, 1, 0]);
+
+        // Counted ANSI sequences cover the whole sequence
+        let graphemes = collect_graphemes("\x1b[31mé\x1b[0m", true).unwrap();
+        let spans: Vec<(usize, usize), 3> = graphemes
+            .iter()
+            .map(|g| (g.byte_offset(), g.byte_len()))
+            .collect();
+        assert_eq!(spans, [(0, 5), (5, 2), (7, 4)]);
+    }
+
+    #[test]
+    fn test_width() {
+        let widths = |text| {
+            let graphemes = collect_graphemes(text, false).unwrap();
+            graphemes
+                .iter()
+                .map(|g| g.width())
+                .collect::<Vec<usize, 8>>()
+        };
+        assert_eq!(widths("a"), [1]);
+        assert_eq!(widths("世"), [2]);
+        assert_eq!(widths("👋"), [2]);
+        assert_eq!(widths("e\u{0301}"), [1]);
+        assert_eq!(widths("👨‍👩‍👧‍👦"), [2]);
+        assert_eq!(widths("각"), [2]);
+
+        // Ambiguous width depends on context
+        let graphemes = collect_graphemes("§", false).unwrap();
+        assert_eq!(graphemes[0].width(), 1);
+        assert_eq!(graphemes[0].width_cjk(), 2);
+        let cjk = WidthConfig {
+            ambiguous_wide: true,
+        };
+        for (text, narrow, wide) in [("§", 1, 2), ("α", 1, 2), ("a", 1, 1), ("世", 2, 2)] {
+            let graphemes = collect_graphemes(text, false).unwrap();
+            assert_eq!(
+                graphemes[0].width_with(WidthConfig::default()),
+                narrow,
+                "{text}"
+            );
+            assert_eq!(graphemes[0].width_with(cjk), wide, "{text}");
+        }
+
+        // Counted ANSI sequences take no columns
+        let graphemes = collect_graphemes("\x1b[31mx", true).unwrap();
+        assert_eq!(graphemes[0].width(), 0);
+    }
+
+    #[test]
+    fn test_pictographic_zwj() {
+        let graphemes = collect_graphemes("👨‍👩‍👧‍👦", false).unwrap();
+        assert_eq!(graphemes.len(), 1);
+
+        // Rainbow flag: white flag, VS16, ZWJ, rainbow
+        let graphemes = collect_graphemes("🏳️‍🌈", false).unwrap();
+        assert_eq!(graphemes.len(), 1);
+        assert_eq!(graphemes[0].len(), 4);
+
+        // Pictographics outside the old emoji ranges: heart on fire, woman with white cane
+        let graphemes = collect_graphemes("❤️‍🔥👩‍🦯", false).unwrap();
+        assert_eq!(graphemes.len(), 2);
+
+        // Skin tone modifiers count as extend before the ZWJ
+        let graphemes = collect_graphemes("👩🏽‍💻", false).unwrap();
+        assert_eq!(graphemes.len(), 1);
+    }
+
+    #[test]
+    fn test_zwj_between_non_pictographics() {
+        let graphemes = collect_graphemes("世\u{200D}界", false).unwrap();
+        assert_eq!(graphemes.len(), 2);
+        assert_eq!(graphemes[0].as_chars(), &['世', '\u{200D}']);
+
+        // A ZWJ only joins a pictographic when the cluster started with one
+        let graphemes = collect_graphemes("a\u{200D}👋", false).unwrap();
+        assert_eq!(graphemes.len(), 2);
+        let graphemes = collect_graphemes("\u{200D}👋", false).unwrap();
+        assert_eq!(graphemes.len(), 2);
+    }
+
     #[test]
     fn test_ansi() {
         let text = "\x1b[31mred\x1b[0m";
@@ -374,6 +1347,91 @@ mod tests {
         assert_eq!(graphemes.len(), 5); // 2 ANSI sequences + "red"
     }
 
+    #[test]
+    fn test_csi() {
+        let inputs = [
+            "\x1b[38;5;200m",
+            "\x1b[?25h",
+            "\x1b[1 q",   // Intermediate byte before the final byte
+            "\x1b[3~",    // Non-alphabetic final byte
+            "\x1b[<0;1M", // Private parameter bytes
+        ];
+        for input in inputs {
+            let graphemes = collect_graphemes(input, true).unwrap();
+            assert_eq!(graphemes.len(), 1, "{input:?}");
+            assert_eq!(graphemes[0].byte_len(), input.len(), "{input:?}");
+            assert!(collect_graphemes(input, false).unwrap().is_empty());
+        }
+
+        // Text after the final byte is not part of the sequence
+        let graphemes = collect_graphemes("\x1b[2~x", true).unwrap();
+        assert_eq!(graphemes.len(), 2);
+        assert_eq!(graphemes[1].as_chars(), &['x']);
+    }
+
+    #[test]
+    fn test_malformed_csi() {
+        // Parameter byte after an intermediate byte
+        let result = collect_graphemes("\x1b[1 2m", true);
+        assert!(matches!(
+            result,
+            Err(GraphemeError::InvalidAnsiSequence {
+                offset: 4,
+                sequence_len: 1
+            })
+        ));
+
+        // Control character inside the sequence
+        let result = collect_graphemes("ab\x1b[1\nm", true);
+        assert!(matches!(
+            result,
+            Err(GraphemeError::InvalidAnsiSequence {
+                offset: 5,
+                sequence_len: 1
+            })
+        ));
+
+        // The malformed byte is consumed, iteration resumes after it
+        let mut iter = GraphemeIterator::new("\x1b[1 2mx", false);
+        assert!(iter.next().unwrap().is_err());
+        assert_eq!(iter.next().unwrap().unwrap().as_chars(), &['m']);
+        assert_eq!(iter.next().unwrap().unwrap().as_chars(), &['x']);
+        assert!(iter.next().is_none());
+    }
+
+    #[test]
+    fn test_osc() {
+        // Hyperlink with ST terminators
+        let text = "\x1b]8;;https://example.com\x1b\\link\x1b]8;;\x1b\\";
+        let graphemes = collect_graphemes(text, true).unwrap();
+        assert_eq!(graphemes.len(), 6); // OSC + "link" + OSC
+        assert_eq!(graphemes[0].as_chars(), &['\x1b']);
+        assert_eq!(graphemes[0].byte_len(), 26);
+        assert_eq!(graphemes[1].as_chars(), &['l']);
+        assert_eq!(collect_graphemes(text, false).unwrap().len(), 4);
+
+        // Window title with BEL and ST terminators
+        for text in ["\x1b]0;title\x07ab", "\x1b]0;title\x1b\\ab"] {
+            let graphemes = collect_graphemes(text, true).unwrap();
+            assert_eq!(graphemes.len(), 3);
+            assert_eq!(graphemes[0].byte_len(), text.len() - 2);
+            assert_eq!(graphemes[1].as_chars(), &['a']);
+            assert_eq!(collect_graphemes(text, false).unwrap().len(), 2);
+        }
+
+        // An escape that isn't a string terminator abandons the OSC
+        let graphemes = collect_graphemes("\x1b]0;title\x1b[0mab", true).unwrap();
+        assert_eq!(graphemes.len(), 3);
+        assert_eq!(graphemes[0].as_chars(), &['\x1b']);
+        assert_eq!(graphemes[1].as_chars(), &['a']);
+
+        let result = collect_graphemes("\x1b]0;t\u{00ED}tulo\x07", true);
+        assert!(matches!(
+            result,
+            Err(GraphemeError::InvalidAnsiSequence { .. })
+        ));
+    }
+
     #[test]
     fn test_invalid_ansi() {
         let text = "\x1b\u{1234}"; // Invalid non-ASCII in ANSI sequence
@@ -384,6 +1442,300 @@ mod tests {
         ));
     }
 
+    #[test]
+    fn test_from_utf8() {
+        let text = "héllo 👋🏽 世界\r\n\x1b[1m!";
+        for count_ansi in [false, true] {
+            let expected = collect_graphemes(text, count_ansi).unwrap();
+            let mut iter = GraphemeIterator::from_utf8(text.as_bytes(), count_ansi);
+            for grapheme in &expected {
+                let actual = iter.next().unwrap().unwrap();
+                assert_eq!(actual.as_chars(), grapheme.as_chars());
+                assert_eq!(actual.byte_offset(), grapheme.byte_offset());
+            }
+            assert!(iter.next().is_none());
+        }
+    }
+
+    #[test]
+    fn test_from_utf8_invalid() {
+        // Truncated four-byte sequence at the end of the buffer
+        let bytes = b"ab\xF0\x9F\x91";
+        let mut iter = GraphemeIterator::from_utf8(bytes, false);
+        assert_eq!(iter.next().unwrap().unwrap().as_chars(), &['a']);
+        assert_eq!(iter.next().unwrap().unwrap().as_chars(), &['b']);
+        assert_eq!(
+            iter.next().unwrap().unwrap_err(),
+            GraphemeError::invalid_utf8(2, 3)
+        );
+        assert!(iter.next().is_none());
+
+        let mut iter = GraphemeIterator::from_utf8(bytes, false);
+        assert_eq!(
+            iter.next_back().unwrap().unwrap_err(),
+            GraphemeError::invalid_utf8(2, 3)
+        );
+        assert_eq!(iter.next_back().unwrap().unwrap().as_chars(), &['b']);
+
+        // Iteration ends at the first malformed byte
+        let mut iter = GraphemeIterator::from_utf8(b"e\xCC\x81\xFFb", false);
+        assert_eq!(iter.next().unwrap().unwrap().as_chars(), &['e', '\u{0301}']);
+        assert_eq!(
+            iter.next().unwrap().unwrap_err(),
+            GraphemeError::invalid_utf8(3, 1)
+        );
+        assert!(iter.next().is_none());
+
+        let mut iter = GraphemeIterator::from_utf8(b"\x80", false);
+        assert_eq!(
+            iter.next().unwrap().unwrap_err(),
+            GraphemeError::invalid_utf8(0, 1)
+        );
+        assert!(iter.next().is_none());
+    }
+
+    #[test]
+    fn test_count_graphemes() {
+        let corpus = [
+            "The quick brown fox jumps over the lazy dog. ",
+            "Hello 👋 世界! Café école garçon über niño año. ",
+            "\x1b[31mred\x1b[0m \x1b[32mgreen\x1b[0m \x1b[34mblue\x1b[0m ",
+            "👨‍👩‍👧‍👦 👩🏽‍💻 🇺🇸 🌈 ",
+            "مرحباً بالعالم العربي كيف حالك؟ ",
+            "\x1b[1m\x1b[31mBold Red\x1b[0m\x1b[0m \x1b[4m\x1b[32mUnderline Green\x1b[0m\x1b[0m ",
+            "a\u{0301}\u{0302}\u{0303} e\u{0301}\u{0304} i\u{0302}\u{0300} o\u{0303}\u{0301} ",
+            "👨🏽‍💻 👩🏾‍🏫 👨🏿‍🌾 👩🏻‍🔬 👨🏼‍🎨 ",
+            "Hello མཐའི་རྒྱ עברית العربية 한글 ไทย ",
+            "a\u{200D}b👨‍👩\u{200D}👧\u{200D}👦क्षि ",
+            "\x1b[31mRed\x1b[0m\n\x1b[32mGreen\x1b[0m\t\x1b[34mBlue\x1b[0m ",
+            "a\u{0301}\u{0302}b\u{0301}\u{0303}c\u{0304}\u{0305}नमस्ते ",
+            include_str!("../benches/texts/arabic.txt"),
+            include_str!("../benches/texts/english.txt"),
+            include_str!("../benches/texts/hindi.txt"),
+            include_str!("../benches/texts/japanese.txt"),
+            include_str!("../benches/texts/korean.txt"),
+            include_str!("../benches/texts/mandarin.txt"),
+            include_str!("../benches/texts/russian.txt"),
+            include_str!("../benches/texts/source_code.txt"),
+        ];
+
+        for text in corpus {
+            for count_ansi in [false, true] {
+                let expected = GraphemeIterator::new(text, count_ansi).count();
+                let counted = GraphemeIterator::count_graphemes(text, count_ansi);
+                assert_eq!(counted, Ok(expected), "{:?}", &text[..text.len().min(16)]);
+
+                let expected = GraphemeIterator::new_lossy(text, count_ansi).count();
+                let counted = GraphemeIterator::new_lossy(text, count_ansi).try_count();
+                assert_eq!(counted, Ok(expected));
+            }
+        }
+    }
+
+    #[test]
+    fn test_count_graphemes_errors() {
+        let text = "ab\x1b\u{1234}c";
+        let err = GraphemeIterator::new(text, true).find_map(|g| g.err());
+        assert_eq!(
+            GraphemeIterator::count_graphemes(text, true),
+            Err(err.unwrap())
+        );
+
+        let text = "a\u{0301}\u{0302}\u{0303}\u{0304}\u{0305}\u{0306}\u{0307}\u{0308}";
+        let err = GraphemeIterator::count_graphemes(text, false).unwrap_err();
+        assert!(matches!(
+            err,
+            GraphemeError::BufferOverflow { offset: 15, .. }
+        ));
+        assert_eq!(GraphemeIterator::new_lossy(text, false).try_count(), Ok(2));
+
+        let iter = GraphemeIterator::from_utf8(b"ab\xFF", false);
+        assert_eq!(iter.try_count(), Err(GraphemeError::invalid_utf8(2, 1)));
+    }
+
+    #[test]
+    fn test_nth() {
+        let input = "Hi 👋🏽 a\u{0301}🇺🇸!";
+        let graphemes = collect_graphemes(input, false).unwrap();
+
+        let nth = GraphemeIterator::new(input, false).nth(3).unwrap().unwrap();
+        assert_eq!(nth.as_chars(), graphemes[3].as_chars());
+        assert_eq!(nth.byte_offset(), graphemes[3].byte_offset());
+
+        // Iteration continues after the yielded item
+        let mut iter = GraphemeIterator::new(input, false);
+        assert_eq!(iter.nth(1).unwrap().unwrap().as_chars(), ['i']);
+        assert_eq!(iter.nth(3).unwrap().unwrap().as_chars(), ['a', '\u{0301}']);
+        assert_eq!(iter.next().unwrap().unwrap().as_chars(), ['🇺', '🇸']);
+        assert!(iter.nth(1).is_none());
+        assert!(iter.next().is_none());
+    }
+
+    #[test]
+    fn test_nth_matches_next() {
+        let inputs = [
+            "\x1b[1mab\x1b[0m",
+            "a\x1b\u{1234}b",
+            "a\u{0301}\u{0302}\u{0303}b",
+        ];
+
+        for input in inputs {
+            for count_ansi in [false, true] {
+                let count = GraphemeIterator::<2>::with_buffer_size(input, count_ansi).count();
+                for n in 0..=count {
+                    let mut iter = GraphemeIterator::<2>::with_buffer_size(input, count_ansi);
+                    let mut expected = GraphemeIterator::<2>::with_buffer_size(input, count_ansi);
+                    for _ in 0..n {
+                        expected.next();
+                    }
+
+                    let (actual, expected) = (iter.nth(n), expected.next());
+                    match (actual, expected) {
+                        (Some(Ok(a)), Some(Ok(b))) => assert_eq!(a.byte_offset(), b.byte_offset()),
+                        (Some(Err(a)), Some(Err(b))) => assert_eq!(a, b),
+                        (None, None) => {}
+                        (a, b) => panic!("nth({n}) of {input:?} gave {a:?}, expected {b:?}"),
+                    }
+                }
+            }
+        }
+
+        let mut iter = GraphemeIterator::from_utf8(b"ab\xff", false);
+        assert!(iter.nth(2).unwrap().is_err());
+        assert!(iter.next().is_none());
+        let mut iter = GraphemeIterator::from_utf8(b"ab\xff", false);
+        assert!(iter.nth(3).is_none());
+        assert!(iter.next().is_none());
+    }
+
+    #[test]
+    fn test_peek() {
+        let text = "a👋🏽\x1b[1mb";
+        let mut iter = GraphemeIterator::new(text, false);
+        assert_eq!(iter.next().unwrap().unwrap(), "a");
+
+        assert_eq!(iter.position(), 1);
+        let peeked = *iter.peek().unwrap().as_ref().unwrap();
+        assert_eq!(iter.peek().unwrap().as_ref().unwrap(), &peeked);
+        assert_eq!(iter.position(), 1);
+
+        let next = iter.next().unwrap().unwrap();
+        assert_eq!(next, peeked);
+        assert_eq!(next, "👋🏽");
+        assert_eq!(next.byte_offset(), 1);
+        assert_eq!(iter.position(), 9);
+
+        // The skipped sequence is consumed by reading the next item
+        assert_eq!(iter.peek().unwrap().as_ref().unwrap().byte_offset(), 13);
+        assert_eq!(iter.position(), 9);
+        assert_eq!(iter.next().unwrap().unwrap(), "b");
+        assert_eq!(iter.position(), text.len());
+
+        assert!(iter.peek().is_none());
+        assert!(iter.next().is_none());
+    }
+
+    #[test]
+    fn test_peek_interactions() {
+        let text = "ab\x1b\u{1234}c";
+
+        let mut iter = GraphemeIterator::new(text, true);
+        iter.peek();
+        assert_eq!(iter.nth(1).unwrap().unwrap(), "b");
+
+        let mut iter = GraphemeIterator::new(text, true);
+        iter.nth(1);
+        assert!(iter.peek().unwrap().is_err());
+        assert!(iter.try_count().is_err());
+
+        let mut iter = GraphemeIterator::new(text, true);
+        iter.peek();
+        assert_eq!(iter.next_back().unwrap().unwrap(), "c");
+        assert_eq!(iter.next_back().unwrap().unwrap(), "\u{1234}");
+        assert!(iter.next_back().unwrap().is_err());
+        assert_eq!(iter.next_back().unwrap().unwrap(), "b");
+        assert_eq!(iter.next_back().unwrap().unwrap(), "a");
+        assert!(iter.next_back().is_none());
+        assert!(iter.next().is_none());
+
+        let mut iter = GraphemeIterator::new("ab", false);
+        iter.peek();
+        assert_eq!(iter.try_count(), Ok(2));
+    }
+
+    #[test]
+    fn test_with_position() {
+        let mut iter = GraphemeIterator::new("a👋b", false).with_position();
+        assert!(matches!(iter.next(), Some(Ok((0, g))) if g == "a"));
+        assert!(matches!(iter.next(), Some(Ok((1, g))) if g == "👋"));
+        assert!(matches!(iter.next(), Some(Ok((5, g))) if g == "b"));
+        assert!(iter.next().is_none());
+
+        let offsets: Vec<_, 4> = GraphemeIterator::new("a👋b", false)
+            .with_position()
+            .rev()
+            .map(|item| item.unwrap().0)
+            .collect();
+        assert_eq!(offsets, [5, 1, 0]);
+
+        // Errors pass through, and counted ANSI sequences have offsets of their own
+        let mut iter = GraphemeIterator::new("\x1b[1mab\x1b\u{1234}", true).with_position();
+        assert!(matches!(iter.next(), Some(Ok((0, _)))));
+        assert!(matches!(iter.next(), Some(Ok((4, _)))));
+        assert!(matches!(iter.next(), Some(Ok((5, _)))));
+        assert!(matches!(
+            iter.next(),
+            Some(Err(GraphemeError::InvalidAnsiSequence { .. }))
+        ));
+    }
+
+    #[test]
+    fn test_with_position_reverse() {
+        // Clusters of several sizes between escape sequences, BELs and backslashes
+        let text = "C:\\é👋🏽\x1b[1m\\\x07e\u{0301}\x1b]0;t\x1b\\\r\n";
+
+        // Walking back covers the whole text, each cluster the one forward iteration finds
+        let mut end = text.len();
+        for item in GraphemeIterator::new(text, true).with_position().rev() {
+            let (offset, grapheme) = item.unwrap();
+            assert_eq!(offset + grapheme.byte_len(), end);
+            let forward = GraphemeIterator::new(&text[offset..], true).next();
+            assert!(matches!(forward, Some(Ok(g)) if g.as_chars() == grapheme.as_chars()));
+            end = offset;
+        }
+        assert_eq!(end, 0);
+    }
+
+    #[test]
+    fn test_as_str() {
+        let mut iter = GraphemeIterator::new("abc👋", false);
+        assert_eq!(iter.as_str(), "abc👋");
+        iter.next();
+        iter.next();
+        assert_eq!(iter.as_str(), "c👋");
+
+        // Peeked items aren't consumed yet
+        assert!(iter.peek().is_some());
+        assert_eq!(iter.as_str(), "c👋");
+        iter.next();
+        assert_eq!(iter.as_str(), "👋");
+        iter.next_back();
+        assert_eq!(iter.as_str(), "");
+
+        // Uncounted ANSI sequences are skipped along with the item after them
+        let mut iter = GraphemeIterator::new("a\x1b[1mb\x1b[0m", false);
+        iter.next();
+        assert_eq!(iter.as_str(), "\x1b[1mb\x1b[0m");
+        iter.next();
+        assert_eq!(iter.as_str(), "\x1b[0m");
+
+        // The malformed tail of byte input is left out
+        let mut iter = GraphemeIterator::from_utf8(b"ab\xF0\x9F", false);
+        assert_eq!(iter.as_str(), "ab");
+        assert_eq!(iter.by_ref().count(), 3);
+        assert_eq!(iter.as_str(), "");
+    }
+
     #[test]
     fn test_buffer_overflow() {
         // Create string with too many combining marks
@@ -392,6 +1744,165 @@ mod tests {
         assert!(matches!(result, Err(GraphemeError::BufferOverflow { .. })));
     }
 
+    #[test]
+    fn test_reverse_matches_forward() {
+        let inputs = [
+            "",
+            "abc",
+            "Hello 👋🏽",
+            "👨‍👩‍👧‍👦 family",
+            "e\u{0301}a\u{0308}",
+            "a\r\nb\n\r",
+            "\u{1100}\u{1161}\u{11A8}한국어",
+            "\u{0600}1 \u{0915}\u{093E}",
+            "🇺🇸🇬🇧",
+            "日本語テキスト",
+        ];
+        for input in inputs {
+            assert_rev_matches(input, false);
+            assert_rev_matches(input, true);
+        }
+    }
+
+    #[test]
+    fn test_reverse_ansi() {
+        let inputs = [
+            "\x1b[31mred\x1b[0m",
+            "\x1b[1;32mé\x1b[0m\u{0301}",
+            "plain 123m text",
+            "\x1b[31mx",
+            "x\x1b[31",
+            "\x1b[\x1b[0mab",
+            "\x1b]8;;https://example.com\x1b\\link\x1b]8;;\x1b\\",
+            "\x1b]0;title\x07ab",
+            "\x1b]0;title\x1b\\ab",
+            "\x1b]0;title",
+            "\x1b]0;a\x1b\\12",
+            "x\x07y\\z",
+            "\x1b[38;5;200mab\x1b[?25h",
+            "\x1b[1 q\u{00E9}\x1b[3~",
+            "1 2 3\x1b[0m4",
+        ];
+        for input in inputs {
+            assert_rev_matches(input, false);
+            assert_rev_matches(input, true);
+        }
+
+        let graphemes = collect_graphemes_rev("\x1b[31mred\x1b[0m", true).unwrap();
+        assert_eq!(graphemes.len(), 5);
+        assert_eq!(graphemes[0].as_chars(), &['\x1b']);
+        assert_eq!(graphemes[1].as_chars(), &['d']);
+    }
+
+    #[test]
+    fn test_reverse_long_ascii_runs() {
+        // Backslashes and BELs may end an OSC sequence, so walking back over each one
+        // looks for an escape. That search must not rescan the whole run every time.
+        const PATTERN: &[u8] = b"C:\\Users\\x\x07";
+        let mut buf = [0; 60_000];
+        for (i, b) in buf.iter_mut().enumerate() {
+            *b = PATTERN[i % PATTERN.len()];
+        }
+        let text = core::str::from_utf8(&buf).unwrap();
+
+        // Every character is a cluster of its own
+        for count_ansi in [false, true] {
+            let forward = GraphemeIterator::new(text, count_ansi);
+            assert!(forward.map(|g| g.unwrap().byte_offset()).eq(0..text.len()));
+            let reverse = GraphemeIterator::new(text, count_ansi).rev();
+            let offsets = reverse.map(|g| g.unwrap().byte_offset());
+            assert!(offsets.eq((0..text.len()).rev()));
+        }
+
+        // An unterminated OSC sequence swallowing the whole run
+        buf[..2].copy_from_slice(b"\x1b]");
+        let text = core::str::from_utf8(&buf).unwrap();
+        for count_ansi in [false, true] {
+            let forward = GraphemeIterator::new(text, count_ansi).count();
+            let reverse = GraphemeIterator::new(text, count_ansi).rev().count();
+            assert_eq!(reverse, forward);
+        }
+    }
+
+    #[test]
+    fn test_mixed_direction() {
+        let mut iter = GraphemeIterator::new("ab👋🏽c\r\n", false);
+        assert_eq!(iter.next_back().unwrap().unwrap().as_chars(), &['\r', '\n']);
+        assert_eq!(iter.next().unwrap().unwrap().as_chars(), &['a']);
+        assert_eq!(iter.next_back().unwrap().unwrap().as_chars(), &['c']);
+        assert_eq!(iter.next_back().unwrap().unwrap().as_chars(), &['👋', '🏽']);
+        assert_eq!(iter.next().unwrap().unwrap().as_chars(), &['b']);
+        assert!(iter.next().is_none());
+        assert!(iter.next_back().is_none());
+    }
+
+    #[test]
+    fn test_reverse_buffer_overflow() {
+        let text = "a\u{0301}\u{0302}\u{0303}\u{0304}\u{0305}\u{0306}\u{0307}\u{0308}\u{0309}b";
+        let mut iter = GraphemeIterator::new(text, false);
+        assert_eq!(iter.next_back().unwrap().unwrap().as_chars(), &['b']);
+        assert!(matches!(
+            iter.next_back(),
+            Some(Err(GraphemeError::BufferOverflow { .. }))
+        ));
+        assert!(iter.next_back().is_none());
+    }
+
+    #[test]
+    fn test_larger_buffer() {
+        // Zalgo-style stack of 20 combining marks on one base character
+        let mut text = heapless::String::<64>::new();
+        text.push('a').unwrap();
+        for mark in '\u{0300}'..='\u{0313}' {
+            text.push(mark).unwrap();
+        }
+        let result = collect_graphemes(&text, false);
+        assert!(matches!(result, Err(GraphemeError::BufferOverflow { .. })));
+
+        let mut iter = GraphemeIterator::<32>::with_buffer_size(&text, false);
+        let grapheme = iter.next().unwrap().unwrap();
+        assert_eq!(grapheme.len(), 21);
+        assert_eq!(grapheme.as_chars()[0], 'a');
+        assert!(iter.next().is_none());
+
+        let mut iter = GraphemeIterator::<32>::with_buffer_size(&text, false);
+        assert_eq!(iter.next_back().unwrap().unwrap().len(), 21);
+    }
+
+    #[test]
+    fn test_lossy() {
+        let mut text = heapless::String::<64>::new();
+        text.push('a').unwrap();
+        for mark in '\u{0300}'..='\u{0313}' {
+            text.push(mark).unwrap();
+        }
+
+        let mut graphemes: Vec<Grapheme, TEST_VEC_SIZE> = Vec::new();
+        for result in GraphemeIterator::new_lossy(&text, false) {
+            graphemes.push(result.unwrap()).unwrap();
+        }
+        assert_eq!(graphemes.len(), 3);
+        assert_eq!(graphemes[0].len(), MAX_GRAPHEME_SIZE);
+        assert_eq!(graphemes[0].as_chars()[0], 'a');
+        assert_eq!(graphemes[1].len(), MAX_GRAPHEME_SIZE);
+        assert_eq!(graphemes[2].len(), 5);
+        assert_eq!(graphemes[1].byte_offset(), graphemes[0].byte_len());
+
+        // Reverse iteration splits the same way
+        let mut iter = GraphemeIterator::new_lossy(&text, false);
+        assert_eq!(iter.next_back().unwrap().unwrap().len(), 5);
+        assert_eq!(iter.next_back().unwrap().unwrap().len(), MAX_GRAPHEME_SIZE);
+        assert_eq!(iter.next_back().unwrap().unwrap().len(), MAX_GRAPHEME_SIZE);
+        assert!(iter.next_back().is_none());
+
+        // Clusters that fit are unaffected
+        let mut iter = GraphemeIterator::new_lossy("e\u{0301}👨‍👩‍👧‍👦!", false);
+        assert_eq!(iter.next().unwrap().unwrap().len(), 2);
+        assert_eq!(iter.next().unwrap().unwrap().len(), 7);
+        assert_eq!(iter.next().unwrap().unwrap().as_chars(), &['!']);
+        assert!(iter.next().is_none());
+    }
+
     // New test to verify heapless Vec capacity handling
     #[test]
     fn test_vec_capacity() {
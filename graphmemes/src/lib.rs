@@ -18,6 +18,10 @@
 //! - Efficient boundary detection using bit patterns
 //! - Support for complex emoji sequences
 //! - Optional ANSI sequence handling
+//! - Terminal display width of clusters, and padding text to a fixed width
+//! - Region codes of flag emoji
+//! - Word iteration following UAX #29 word boundary rules
+//! - Optional `serde` support, serializing clusters as strings
 //! - Compliant with Unicode Standard Annex #29
 //!
 //! # Example
@@ -36,8 +40,8 @@
 //!
 //! # Implementation Details
 //!
-//! The crate uses a fixed-size buffer ([`MAX_GRAPHEME_SIZE`]) to handle grapheme clusters,
-//! which is sufficient for even complex emoji sequences. Boundary detection is performed
+//! The crate uses a fixed-size buffer ([`MAX_GRAPHEME_SIZE`] by default) to handle grapheme
+//! clusters, which is sufficient for even complex emoji sequences. Boundary detection is performed
 //! using efficient bit patterns and follows the rules specified in Unicode Standard
 //! Annex #29.
 //!
@@ -49,19 +53,42 @@
 //! This crate is `no_std` compatible and makes no heap allocations. All operations
 //! use fixed-size buffers and stack-only data structures.
 
+mod emoji;
 mod error;
 mod grapheme;
 mod iter;
+mod pad;
+mod search;
+#[cfg(feature = "serde")]
+mod serde_impl;
+mod slice;
+mod take_width;
+mod token;
+mod visible;
+mod width;
+mod word;
+mod write;
 
+pub use emoji::{flag_code, SkinTone};
 pub use error::{GraphemeError, Result};
 pub use grapheme::{boundary, Grapheme};
 pub use iter::GraphemeIterator;
+pub use pad::{pad_to_width, Align};
+pub use search::rfind_grapheme;
+pub use slice::{grapheme_index_of_byte, grapheme_slice, is_grapheme_boundary, safe_split_at};
+pub use take_width::TakeWidth;
+pub use token::{Token, Tokens};
+pub use visible::{visible_spans, VisibleSpans};
+pub use width::WidthConfig;
+pub use word::{Word, WordIterator};
+pub use write::{coalesce_ansi, reverse_graphemes, write_graphemes};
 
-/// Maximum number of code points in a grapheme cluster.
+/// Default maximum number of code points in a grapheme cluster.
 ///
-/// This constant defines the size of the fixed buffer used to store grapheme clusters.
+/// This constant defines the default size of the fixed buffer used to store grapheme clusters.
 /// The value 8 is chosen to accommodate complex emoji sequences while maintaining
-/// reasonable stack usage.
+/// reasonable stack usage. Text with longer clusters, such as deeply stacked combining
+/// marks, can use a larger buffer via [`GraphemeIterator::with_buffer_size`].
 ///
 /// Common sequences that fit within this limit:
 /// - Basic emoji: 1-2 code points
@@ -69,4 +96,4 @@ pub use iter::GraphemeIterator;
 /// - Family emoji: 7-8 code points
 /// - Flag emoji: 2 code points
 /// - Characters with combining marks: 2-3 code points
-pub(crate) const MAX_GRAPHEME_SIZE: usize = 8;
+pub const MAX_GRAPHEME_SIZE: usize = 8;
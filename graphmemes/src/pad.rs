@@ -0,0 +1,156 @@
+//! Padding text to a fixed display width.
+//!
+//! This module fits text into a fixed number of terminal columns, for aligning table cells
+//! and similar layouts. Text is measured by the display width of its grapheme clusters, so
+//! wide and zero-width clusters line up correctly, and text that is too wide is cut at a
+//! cluster boundary.
+
+use core::fmt::Write;
+
+use crate::{GraphemeError, GraphemeIterator, Result};
+
+/// Horizontal alignment of text within a padded area.
+///
+/// See [`pad_to_width`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Align {
+    /// Text at the start of the area, padding after it
+    #[default]
+    Left,
+    /// Text at the end of the area, padding before it
+    Right,
+    /// Text in the middle of the area, with any odd column of padding after it
+    Center,
+}
+
+/// Writes `text` to `out`, padded or truncated to exactly `width` terminal columns.
+///
+/// Text narrower than `width` is padded with `fill` according to `align`. Text that is too
+/// wide is truncated to the clusters that fit, as by
+/// [`take_width`](GraphemeIterator::take_width), and any column a wide cluster couldn't
+/// fill is padded as well. Clusters are measured with [`Grapheme::width`](crate::Grapheme::width).
+///
+/// ANSI escape sequences occupy no columns and are written as they are, except for those after
+/// the point the text is truncated at.
+///
+/// # Arguments
+///
+/// * `text` - The text to write
+/// * `width` - The number of columns to fill
+/// * `align` - Where to place the text within the columns
+/// * `fill` - The character to pad with, which should occupy a single column
+/// * `out` - The output to write to
+///
+/// # Returns
+///
+/// An error if the written part of `text` produces one, or `GraphemeError::WriteFailed` if
+/// `out` rejects a write. Failed writes of padding are reported as empty sequences at the
+/// offset the padding belongs at.
+///
+/// # Examples
+///
+/// ```
+/// use graphmemes::{pad_to_width, Align, Result};
+///
+/// # fn main() -> Result<()> {
+/// let mut out = String::new();
+/// pad_to_width("世", 5, Align::Center, '.', &mut out)?;
+/// assert_eq!(out, ".世..");
+///
+/// out.clear();
+/// pad_to_width("ab世界", 5, Align::Left, ' ', &mut out)?;
+/// assert_eq!(out, "ab世 ");
+/// # Ok(())
+/// # }
+/// ```
+pub fn pad_to_width(
+    text: &str,
+    width: usize,
+    align: Align,
+    fill: char,
+    out: &mut impl Write,
+) -> Result<()> {
+    let mut iter = GraphemeIterator::new(text, true).take_width(width);
+    let mut end = 0;
+    for item in iter.by_ref() {
+        let grapheme = item?;
+        end = grapheme.byte_offset() + grapheme.byte_len();
+    }
+
+    let padding = width - iter.used_width();
+    let before = match align {
+        Align::Left => 0,
+        Align::Right => padding,
+        Align::Center => padding / 2,
+    };
+
+    write_fill(out, fill, before, 0)?;
+    out.write_str(&text[..end])
+        .map_err(|_| GraphemeError::write_failed(0, end))?;
+    write_fill(out, fill, padding - before, end)
+}
+
+/// Writes `count` copies of `fill`, reporting a failure at `offset`.
+#[inline]
+fn write_fill(out: &mut impl Write, fill: char, count: usize, offset: usize) -> Result<()> {
+    (0..count)
+        .try_for_each(|_| out.write_char(fill))
+        .map_err(|_| GraphemeError::write_failed(offset, 0))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use heapless::String;
+
+    /// Pads `text` into a fresh string
+    fn pad(text: &str, width: usize, align: Align) -> String<32> {
+        let mut out = String::new();
+        pad_to_width(text, width, align, '.', &mut out).unwrap();
+        out
+    }
+
+    #[test]
+    fn test_alignments() {
+        assert_eq!(pad("世", 5, Align::Left), "世...");
+        assert_eq!(pad("世", 5, Align::Right), "...世");
+        assert_eq!(pad("世", 5, Align::Center), ".世..");
+        assert_eq!(pad("ab", 4, Align::Center), ".ab.");
+        assert_eq!(pad("", 3, Align::Right), "...");
+        assert_eq!(pad("ab", 2, Align::Center), "ab");
+    }
+
+    #[test]
+    fn test_cluster_widths() {
+        assert_eq!(pad("e\u{0301}", 3, Align::Right), "..e\u{0301}");
+        assert_eq!(pad("👋🏽", 3, Align::Left), "👋🏽.");
+        assert_eq!(pad("a\u{200B}", 2, Align::Left), "a\u{200B}.");
+    }
+
+    #[test]
+    fn test_truncation() {
+        assert_eq!(pad("abcdef", 3, Align::Right), "abc");
+        // A wide cluster that doesn't fit leaves a column to pad
+        assert_eq!(pad("a世", 2, Align::Left), "a.");
+        assert_eq!(pad("a世", 2, Align::Right), ".a");
+        assert_eq!(pad("世", 0, Align::Center), "");
+    }
+
+    #[test]
+    fn test_ansi() {
+        let text = "\x1b[31m世\x1b[0m";
+        assert_eq!(pad(text, 3, Align::Right), ".\x1b[31m世\x1b[0m");
+        assert_eq!(pad("\x1b[1mab\x1b[0m", 1, Align::Left), "\x1b[1ma");
+    }
+
+    #[test]
+    fn test_errors() {
+        let mut out = String::<32>::new();
+        let result = pad_to_width("ab\x1b\u{1234}c", 8, Align::Left, ' ', &mut out);
+        assert!(matches!(result, Err(GraphemeError::InvalidAnsiSequence { .. })));
+
+        let mut out = String::<4>::new();
+        let result = pad_to_width("ab", 8, Align::Left, ' ', &mut out);
+        assert_eq!(result, Err(GraphemeError::write_failed(2, 0)));
+    }
+}
@@ -0,0 +1,81 @@
+//! Searching strings for grapheme clusters.
+
+use crate::GraphemeIterator;
+
+/// Returns the byte offset of the last grapheme cluster in `text` equal to `needle`.
+///
+/// Clusters are scanned from the front and the last match is kept, so the whole text is
+/// scanned once even when a match lies near its end.
+///
+/// Only whole clusters match, so a needle of a bare base character doesn't match that
+/// character when a combining mark follows it, and vice versa. ANSI escape sequences never
+/// match, and clusters that produce an error are skipped.
+///
+/// # Arguments
+///
+/// * `text` - The text to search
+/// * `needle` - The cluster to search for
+///
+/// # Returns
+///
+/// The byte offset of the start of the last matching cluster, or `None` if no cluster
+/// matches.
+///
+/// # Examples
+///
+/// ```
+/// use graphmemes::rfind_grapheme;
+///
+/// let text = "e\u{0301}te\u{0301}e";
+/// assert_eq!(rfind_grapheme(text, "e\u{0301}"), Some(4));
+/// assert_eq!(rfind_grapheme(text, "e"), Some(7));
+/// assert_eq!(rfind_grapheme(text, "\u{0301}"), None);
+/// ```
+pub fn rfind_grapheme(text: &str, needle: &str) -> Option<usize> {
+    GraphemeIterator::new(text, false)
+        .filter_map(|item| item.ok())
+        .filter(|grapheme| {
+            let offset = grapheme.byte_offset();
+            text[offset..offset + grapheme.byte_len()] == *needle
+        })
+        // Folding keeps the scan forward, where `last` would walk back from the end instead
+        .fold(None, |_, grapheme| Some(grapheme.byte_offset()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_combining_marks() {
+        let text = "cafe\u{0301} ou cafe";
+        assert_eq!(rfind_grapheme(text, "e\u{0301}"), Some(3));
+        assert_eq!(rfind_grapheme(text, "e"), Some(text.len() - 1));
+
+        let text = "e\u{0301}e";
+        assert_eq!(rfind_grapheme(text, "e"), Some(3));
+        assert_eq!(rfind_grapheme("e\u{0301}", "e"), None);
+    }
+
+    #[test]
+    fn test_no_match() {
+        assert_eq!(rfind_grapheme("", "a"), None);
+        assert_eq!(rfind_grapheme("abc", ""), None);
+        assert_eq!(rfind_grapheme("abc", "ab"), None);
+        assert_eq!(rfind_grapheme("👋🏽", "👋"), None);
+    }
+
+    #[test]
+    fn test_ansi() {
+        let text = "a\x1b[31ma\x1b[0m";
+        assert_eq!(rfind_grapheme(text, "a"), Some(6));
+        assert_eq!(rfind_grapheme(text, "\x1b[0m"), None);
+    }
+
+    #[test]
+    fn test_emoji() {
+        let text = "🇺🇸a🇺🇸🇬🇧";
+        assert_eq!(rfind_grapheme(text, "🇺🇸"), Some(9));
+        assert_eq!(rfind_grapheme(text, "🇬🇧"), Some(17));
+    }
+}
@@ -0,0 +1,66 @@
+//! Serde support for grapheme clusters.
+//!
+//! A [`Grapheme`] is serialized as the string formed by its characters, and deserialized
+//! from a string back into its fixed-size buffer. Neither direction allocates.
+
+use core::fmt;
+
+use serde::de::{self, Deserialize, Deserializer, Visitor};
+use serde::ser::{Serialize, Serializer};
+
+use crate::Grapheme;
+
+impl<const N: usize> Serialize for Grapheme<N> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+/// Visitor that fills a grapheme buffer from a string.
+struct GraphemeVisitor<const N: usize>;
+
+impl<'de, const N: usize> Visitor<'de> for GraphemeVisitor<N> {
+    type Value = Grapheme<N>;
+
+    fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "a string of at most {N} characters")
+    }
+
+    fn visit_str<E: de::Error>(self, v: &str) -> Result<Self::Value, E> {
+        Grapheme::try_from(v).map_err(|_| E::invalid_length(v.chars().count(), &self))
+    }
+}
+
+impl<'de, const N: usize> Deserialize<'de> for Grapheme<N> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        deserializer.deserialize_str(GraphemeVisitor)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::GraphemeIterator;
+
+    #[test]
+    fn test_round_trip() {
+        let text = "👨\u{200D}👩\u{200D}👧";
+        let grapheme = GraphemeIterator::new(text, false).next().unwrap().unwrap();
+
+        let json = serde_json::to_string(&grapheme).unwrap();
+        assert_eq!(json, "\"👨\u{200D}👩\u{200D}👧\"");
+
+        let decoded: Grapheme = serde_json::from_str(&json).unwrap();
+        assert_eq!(decoded.as_chars(), grapheme.as_chars());
+        assert_eq!(decoded.byte_len(), text.len());
+    }
+
+    #[test]
+    fn test_over_long() {
+        let json = "\"a\u{0301}\u{0302}\u{0303}\u{0304}\u{0305}\u{0306}\u{0307}\u{0308}\"";
+        assert!(serde_json::from_str::<Grapheme>(json).is_err());
+
+        let decoded: Grapheme<16> = serde_json::from_str(json).unwrap();
+        assert_eq!(decoded.len(), 9);
+    }
+}
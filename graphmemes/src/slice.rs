@@ -0,0 +1,323 @@
+//! Slicing and splitting strings at grapheme cluster boundaries, checking offsets against
+//! them, and mapping byte offsets to cluster indices.
+
+use core::cmp::Ordering;
+use core::ops::Range;
+
+use crate::{GraphemeError, GraphemeIterator};
+
+/// Returns the substring spanning the grapheme clusters in `range`.
+///
+/// Clusters are counted as [`GraphemeIterator`] yields them, so ANSI escape sequences count
+/// as clusters only if `count_ansi` is set. Uncounted sequences between the clusters in the
+/// range are included in the substring, while those before the first or after the last
+/// cluster are not.
+///
+/// An empty range yields an empty substring at the start of the cluster it refers to, or at
+/// the end of the text if it refers to one past the last cluster.
+///
+/// # Arguments
+///
+/// * `text` - The text to slice
+/// * `range` - The range of cluster indices to include
+/// * `count_ansi` - Whether to count ANSI escape sequences as graphemes
+///
+/// # Returns
+///
+/// The substring, or `None` if the range is out of bounds or the text up to the end of the
+/// range produces an error.
+///
+/// # Examples
+///
+/// ```
+/// use graphmemes::grapheme_slice;
+///
+/// let text = "a👋b🇺🇸c";
+/// assert_eq!(grapheme_slice(text, 1..4, false), Some("👋b🇺🇸"));
+/// assert_eq!(grapheme_slice(text, 4..6, false), None);
+/// ```
+pub fn grapheme_slice(text: &str, range: Range<usize>, count_ansi: bool) -> Option<&str> {
+    if range.start > range.end {
+        return None;
+    }
+
+    let mut start = None;
+    let mut count = 0;
+
+    for item in GraphemeIterator::new(text, count_ansi) {
+        let grapheme = item.ok()?;
+        if count == range.start {
+            let offset = grapheme.byte_offset();
+            if range.is_empty() {
+                return Some(&text[offset..offset]);
+            }
+            start = Some(offset);
+        }
+
+        count += 1;
+        if count == range.end && !range.is_empty() {
+            let end = grapheme.byte_offset() + grapheme.byte_len();
+            return Some(&text[start?..end]);
+        }
+    }
+
+    // An empty range may also point just past the last cluster
+    (range.is_empty() && count == range.start).then(|| &text[text.len()..])
+}
+
+/// Returns the number of grapheme clusters that end at or before `byte` in `text`.
+///
+/// This maps a byte offset, such as a text cursor, to a cluster index. An offset inside a
+/// cluster counts that cluster as not yet started, so it maps to the same index as the start
+/// of the cluster, and offsets past the end of the text map to the total number of clusters.
+/// ANSI escape sequences aren't counted, and clusters too long for the grapheme buffer still
+/// count as one cluster each.
+///
+/// # Arguments
+///
+/// * `text` - The text the offset points into
+/// * `byte` - The byte offset to map
+///
+/// # Examples
+///
+/// ```
+/// use graphmemes::grapheme_index_of_byte;
+///
+/// let text = "a👋b";
+/// assert_eq!(grapheme_index_of_byte(text, 1), 1);
+/// assert_eq!(grapheme_index_of_byte(text, 3), 1);
+/// assert_eq!(grapheme_index_of_byte(text, 5), 2);
+/// ```
+pub fn grapheme_index_of_byte(text: &str, byte: usize) -> usize {
+    let mut iter = GraphemeIterator::new(text, false);
+    let mut count = 0;
+    while let Some(item) = iter.next() {
+        // The iterator stops right after the item it returned, even if it was an error
+        if iter.position() > byte {
+            break;
+        }
+        if !matches!(item, Err(GraphemeError::InvalidAnsiSequence { .. })) {
+            count += 1;
+        }
+    }
+    count
+}
+
+/// Returns true if `byte` falls on a grapheme cluster boundary in `text`.
+///
+/// Boundaries are the start and end of the text and the starts of the clusters the iterator
+/// yields, so slicing `text` at a boundary never cuts a cluster in half. ANSI escape sequences
+/// are treated as clusters of their own, so offsets inside one aren't boundaries either.
+/// Offsets past the end of the text never are.
+///
+/// # Arguments
+///
+/// * `text` - The text the offset points into
+/// * `byte` - The byte offset to check
+///
+/// # Examples
+///
+/// ```
+/// use graphmemes::is_grapheme_boundary;
+///
+/// let text = "a👋b";
+/// assert!(is_grapheme_boundary(text, 1));
+/// assert!(!is_grapheme_boundary(text, 3));
+/// assert!(is_grapheme_boundary(text, 5));
+/// ```
+pub fn is_grapheme_boundary(text: &str, byte: usize) -> bool {
+    if byte == 0 || byte == text.len() {
+        return true;
+    }
+
+    let mut iter = GraphemeIterator::new(text, true);
+    while iter.next().is_some() {
+        // The iterator stops right after the item it returned, even if it was an error
+        match iter.position().cmp(&byte) {
+            Ordering::Less => {}
+            Ordering::Equal => return true,
+            Ordering::Greater => return false,
+        }
+    }
+    false
+}
+
+/// Splits `text` at the last grapheme cluster boundary within the first `max_bytes` bytes.
+///
+/// Returns the longest prefix of at most `max_bytes` bytes that doesn't cut a grapheme
+/// cluster or an ANSI escape sequence in half, along with the rest of the text. This lets
+/// large inputs be streamed through a fixed-size buffer chunk by chunk. If even the first
+/// cluster is longer than `max_bytes`, the prefix is empty.
+///
+/// Note that a boundary at the end of a chunk may not be final, since a later character could
+/// still extend the cluster, so the text should be complete or split with a margin.
+///
+/// # Arguments
+///
+/// * `text` - The text to split
+/// * `max_bytes` - The maximum length of the prefix in bytes
+///
+/// # Examples
+///
+/// ```
+/// use graphmemes::safe_split_at;
+///
+/// let text = "ab👋🏽c";
+/// assert_eq!(safe_split_at(text, 5), ("ab", "👋🏽c"));
+/// assert_eq!(safe_split_at(text, 10), ("ab👋🏽", "c"));
+/// ```
+pub fn safe_split_at(text: &str, max_bytes: usize) -> (&str, &str) {
+    if max_bytes >= text.len() {
+        return (text, &text[text.len()..]);
+    }
+
+    let mut iter = GraphemeIterator::new(text, true);
+    let mut end = 0;
+    while iter.next().is_some() {
+        // The iterator stops right after the item it returned, even if it was an error
+        if iter.position() > max_bytes {
+            break;
+        }
+        end = iter.position();
+    }
+    text.split_at(end)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_slices() {
+        let text = "a👋b🇺🇸c";
+        assert_eq!(grapheme_slice(text, 0..5, false), Some(text));
+        assert_eq!(grapheme_slice(text, 0..1, false), Some("a"));
+        assert_eq!(grapheme_slice(text, 1..2, false), Some("👋"));
+        assert_eq!(grapheme_slice(text, 1..4, false), Some("👋b🇺🇸"));
+        assert_eq!(grapheme_slice(text, 3..4, false), Some("🇺🇸"));
+        assert_eq!(grapheme_slice(text, 4..5, false), Some("c"));
+    }
+
+    #[test]
+    fn test_empty_ranges() {
+        let text = "a👋b🇺🇸c";
+        assert_eq!(grapheme_slice(text, 0..0, false), Some(""));
+        assert_eq!(grapheme_slice(text, 2..2, false), Some(""));
+        assert_eq!(grapheme_slice(text, 5..5, false), Some(""));
+        assert_eq!(grapheme_slice("", 0..0, false), Some(""));
+
+        // Empty slices sit at the start of the cluster they refer to
+        let slice = grapheme_slice(text, 2..2, false).unwrap();
+        assert_eq!(slice.as_ptr(), text[5..].as_ptr());
+    }
+
+    #[test]
+    fn test_out_of_bounds() {
+        let text = "a👋b🇺🇸c";
+        assert_eq!(grapheme_slice(text, 4..6, false), None);
+        assert_eq!(grapheme_slice(text, 6..6, false), None);
+        #[allow(clippy::reversed_empty_ranges)]
+        let reversed = 3..2;
+        assert_eq!(grapheme_slice(text, reversed, false), None);
+        assert_eq!(grapheme_slice("", 0..1, false), None);
+    }
+
+    #[test]
+    fn test_ansi() {
+        let text = "\x1b[1ma\x1b[0mb\x1b[0m";
+        assert_eq!(grapheme_slice(text, 0..2, false), Some("a\x1b[0mb"));
+        assert_eq!(grapheme_slice(text, 2..2, false), Some(""));
+        assert_eq!(grapheme_slice(text, 0..1, true), Some("\x1b[1m"));
+        assert_eq!(grapheme_slice(text, 1..4, true), Some("a\x1b[0mb"));
+        assert_eq!(grapheme_slice(text, 4..5, true), Some("\x1b[0m"));
+    }
+
+    #[test]
+    fn test_errors() {
+        let text = "ab\x1b\u{1234}c";
+        assert_eq!(grapheme_slice(text, 0..2, true), Some("ab"));
+        assert_eq!(grapheme_slice(text, 0..3, true), None);
+    }
+
+    #[test]
+    fn test_index_of_byte() {
+        let text = "a👋b";
+        let indices: heapless::Vec<_, 8> =
+            (0..=text.len() + 1).map(|byte| grapheme_index_of_byte(text, byte)).collect();
+        assert_eq!(indices, [0, 1, 1, 1, 1, 2, 3, 3]);
+
+        assert_eq!(grapheme_index_of_byte("", 0), 0);
+        assert_eq!(grapheme_index_of_byte("e\u{0301}", 1), 0);
+        assert_eq!(grapheme_index_of_byte("e\u{0301}", 3), 1);
+    }
+
+    #[test]
+    fn test_index_of_byte_skips_ansi() {
+        let text = "\x1b[1ma\x1b[0mb";
+        assert_eq!(grapheme_index_of_byte(text, 4), 0);
+        assert_eq!(grapheme_index_of_byte(text, 5), 1);
+        assert_eq!(grapheme_index_of_byte(text, 9), 1);
+        assert_eq!(grapheme_index_of_byte(text, 10), 2);
+    }
+
+    #[test]
+    fn test_index_of_byte_errors() {
+        // Overlong clusters still count as one cluster
+        let text = "a\u{0301}\u{0301}\u{0301}\u{0301}\u{0301}\u{0301}\u{0301}\u{0301}b";
+        assert_eq!(grapheme_index_of_byte(text, text.len() - 2), 0);
+        assert_eq!(grapheme_index_of_byte(text, text.len() - 1), 1);
+        assert_eq!(grapheme_index_of_byte(text, text.len()), 2);
+    }
+
+    #[test]
+    fn test_is_grapheme_boundary() {
+        let text = "a👋b";
+        assert!(is_grapheme_boundary(text, 0));
+        assert!(is_grapheme_boundary(text, 1));
+        assert!(!is_grapheme_boundary(text, 3));
+        assert!(is_grapheme_boundary(text, 5));
+        assert!(is_grapheme_boundary(text, 6));
+        assert!(!is_grapheme_boundary(text, 7));
+        assert!(is_grapheme_boundary("", 0));
+    }
+
+    #[test]
+    fn test_is_grapheme_boundary_clusters() {
+        // Char boundaries inside a multi-char cluster aren't cluster boundaries
+        let text = "e\u{0301}👋🏽";
+        assert!(!is_grapheme_boundary(text, 1));
+        assert!(is_grapheme_boundary(text, 3));
+        assert!(!is_grapheme_boundary(text, 7));
+
+        let text = "a\x1b[1mb";
+        assert!(is_grapheme_boundary(text, 1));
+        assert!(!is_grapheme_boundary(text, 3));
+        assert!(is_grapheme_boundary(text, 5));
+    }
+
+    #[test]
+    fn test_safe_split_at() {
+        let text = "ab👋c";
+        assert_eq!(safe_split_at(text, 2), ("ab", "👋c"));
+        // Inside the emoji, the split backs off to its start
+        for max_bytes in 3..6 {
+            assert_eq!(safe_split_at(text, max_bytes), ("ab", "👋c"));
+        }
+        assert_eq!(safe_split_at(text, 6), ("ab👋", "c"));
+        assert_eq!(safe_split_at(text, 7), (text, ""));
+        assert_eq!(safe_split_at(text, 100), (text, ""));
+        assert_eq!(safe_split_at("", 0), ("", ""));
+    }
+
+    #[test]
+    fn test_safe_split_keeps_clusters() {
+        // The first cluster doesn't fit at all
+        assert_eq!(safe_split_at("👋🏽a", 4), ("", "👋🏽a"));
+        assert_eq!(safe_split_at("e\u{0301}x", 1), ("", "e\u{0301}x"));
+        assert_eq!(safe_split_at("a\r\nb", 2), ("a", "\r\nb"));
+
+        // ANSI sequences aren't cut either
+        assert_eq!(safe_split_at("a\x1b[31mb", 4), ("a", "\x1b[31mb"));
+        assert_eq!(safe_split_at("a\x1b[31mb", 6), ("a\x1b[31m", "b"));
+    }
+}
@@ -0,0 +1,229 @@
+//! Width-limited grapheme cluster iteration.
+//!
+//! This module provides an adapter that yields grapheme clusters until a terminal column
+//! budget is used up, for fitting text into a fixed-width area without splitting clusters.
+
+use crate::{Grapheme, GraphemeIterator, Result, WidthConfig, MAX_GRAPHEME_SIZE};
+
+/// Iterator adapter that yields whole grapheme clusters within a column budget.
+///
+/// Created by [`GraphemeIterator::take_width`]. Clusters are yielded in order until the
+/// next cluster would exceed the budget, at which point iteration stops. A cluster is never
+/// split, so a wide cluster that doesn't fit in the remaining columns is left out entirely,
+/// along with everything after it.
+///
+/// Zero-width clusters always fit, so combining sequences, controls and counted ANSI
+/// sequences that follow the last visible cluster are still yielded.
+///
+/// Errors from the underlying iterator are passed through and occupy no columns.
+///
+/// Clusters are measured with [`Grapheme::width`] unless another configuration is set with
+/// [`width_config`](Self::width_config).
+///
+/// # Examples
+///
+/// ```
+/// use graphmemes::{GraphemeIterator, Result};
+///
+/// # fn main() -> Result<()> {
+/// let mut iter = GraphemeIterator::new("ab世界", false).take_width(5);
+/// let graphemes: Vec<_> = iter.by_ref().collect::<Result<_>>()?;
+/// assert_eq!(graphemes.len(), 3); // "a" "b" "世", "界" would need 6 columns
+/// assert_eq!(iter.used_width(), 4);
+/// # Ok(())
+/// # }
+/// ```
+pub struct TakeWidth<'a, const N: usize = MAX_GRAPHEME_SIZE> {
+    /// The underlying grapheme iterator
+    iter: GraphemeIterator<'a, N>,
+    /// Total column budget
+    max_cols: usize,
+    /// Columns used by the clusters yielded so far
+    used: usize,
+    /// How clusters are measured
+    config: WidthConfig,
+    /// Whether a cluster has been found that doesn't fit
+    done: bool,
+}
+
+impl<'a, const N: usize> TakeWidth<'a, N> {
+    /// Creates a new width-limited adapter over a grapheme iterator.
+    #[inline]
+    pub(crate) fn new(iter: GraphemeIterator<'a, N>, max_cols: usize) -> Self {
+        Self {
+            iter,
+            max_cols,
+            used: 0,
+            config: WidthConfig::default(),
+            done: false,
+        }
+    }
+
+    /// Sets how clusters are measured against the budget.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use graphmemes::{GraphemeIterator, WidthConfig};
+    ///
+    /// let cjk = WidthConfig { ambiguous_wide: true };
+    /// let iter = GraphemeIterator::new("§§§", false).take_width(4);
+    /// assert_eq!(iter.width_config(cjk).count(), 2);
+    /// ```
+    #[inline]
+    pub fn width_config(mut self, config: WidthConfig) -> Self {
+        self.config = config;
+        self
+    }
+
+    /// Returns the number of columns used by the clusters yielded so far.
+    #[inline]
+    pub fn used_width(&self) -> usize {
+        self.used
+    }
+
+    /// Returns the number of columns still available.
+    #[inline]
+    pub fn remaining_width(&self) -> usize {
+        self.max_cols - self.used
+    }
+}
+
+impl<'a, const N: usize> Iterator for TakeWidth<'a, N> {
+    type Item = Result<Grapheme<N>>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.done {
+            return None;
+        }
+
+        match self.iter.next()? {
+            Ok(grapheme) => {
+                let width = grapheme.width_with(self.config);
+                if width > self.remaining_width() {
+                    self.done = true;
+                    return None;
+                }
+                self.used += width;
+                Some(Ok(grapheme))
+            }
+            Err(err) => Some(Err(err)),
+        }
+    }
+}
+
+impl<'a, const N: usize> GraphemeIterator<'a, N> {
+    /// Limits iteration to the clusters that fit within `max_cols` terminal columns.
+    ///
+    /// See [`TakeWidth`] for details.
+    ///
+    /// # Arguments
+    ///
+    /// * `max_cols` - The total number of columns available
+    #[inline]
+    pub fn take_width(self, max_cols: usize) -> TakeWidth<'a, N> {
+        TakeWidth::new(self, max_cols)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use heapless::Vec;
+
+    /// Collects the clusters that fit in `max_cols`, along with the columns used
+    fn take(input: &str, count_ansi: bool, max_cols: usize) -> (Vec<Grapheme, 32>, usize) {
+        let mut iter = GraphemeIterator::new(input, count_ansi).take_width(max_cols);
+        let graphemes = iter.by_ref().map(|g| g.unwrap()).collect();
+        (graphemes, iter.used_width())
+    }
+
+    #[test]
+    fn test_ascii_budget() {
+        let (graphemes, used) = take("hello", false, 3);
+        assert_eq!(graphemes.len(), 3);
+        assert_eq!(used, 3);
+
+        let (graphemes, used) = take("hi", false, 10);
+        assert_eq!(graphemes.len(), 2);
+        assert_eq!(used, 2);
+
+        let (graphemes, used) = take("hello", false, 0);
+        assert!(graphemes.is_empty());
+        assert_eq!(used, 0);
+    }
+
+    #[test]
+    fn test_wide_cluster_excluded() {
+        // "世" needs two columns but only one remains
+        let (graphemes, used) = take("a世b", false, 2);
+        assert_eq!(graphemes.len(), 1);
+        assert_eq!(used, 1);
+
+        let (graphemes, used) = take("a世b", false, 3);
+        assert_eq!(graphemes.len(), 2);
+        assert_eq!(used, 3);
+
+        let (graphemes, used) = take("👋🏽👋", false, 3);
+        assert_eq!(graphemes.len(), 1);
+        assert_eq!(graphemes[0].as_chars(), &['👋', '🏽']);
+        assert_eq!(used, 2);
+    }
+
+    #[test]
+    fn test_mixed_budgets() {
+        // Widths: a=1 b=1 世=2 界=2 👋=2 c=1
+        let text = "ab世界👋c";
+        // (budget, clusters yielded, columns used)
+        let expected = [
+            (0, 0, 0),
+            (1, 1, 1),
+            (2, 2, 2),
+            (3, 2, 2),
+            (4, 3, 4),
+            (5, 3, 4),
+            (6, 4, 6),
+            (7, 4, 6),
+            (8, 5, 8),
+            (9, 6, 9),
+            (20, 6, 9),
+        ];
+        for (budget, count, used) in expected {
+            let (graphemes, actual_used) = take(text, false, budget);
+            assert_eq!(graphemes.len(), count, "budget {budget}");
+            assert_eq!(actual_used, used, "budget {budget}");
+        }
+    }
+
+    #[test]
+    fn test_trailing_zero_width() {
+        // Counted ANSI sequences take no columns, so the reset still fits
+        let (graphemes, used) = take("\x1b[31mab\x1b[0mc", true, 2);
+        assert_eq!(graphemes.len(), 4);
+        assert_eq!(graphemes[3].as_chars(), &['\x1b']);
+        assert_eq!(used, 2);
+
+        // Zero-width clusters after an excluded cluster are not yielded
+        let (graphemes, _) = take("a世\x07", false, 2);
+        assert_eq!(graphemes.len(), 1);
+    }
+
+    #[test]
+    fn test_width_config() {
+        let cjk = WidthConfig {
+            ambiguous_wide: true,
+        };
+        let count = |text, config| {
+            let iter = GraphemeIterator::new(text, false).take_width(4);
+            iter.width_config(config).count()
+        };
+
+        // Ambiguous-width characters are narrow by default
+        assert_eq!(count("§§§§§", WidthConfig::default()), 4);
+        assert_eq!(count("§§§§§", cjk), 2);
+
+        // Unambiguous characters are unaffected
+        assert_eq!(count("abcde", cjk), 4);
+        assert_eq!(count("世界世", cjk), 2);
+    }
+}
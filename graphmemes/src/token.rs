@@ -0,0 +1,160 @@
+//! Iteration over grapheme clusters and ANSI escape sequences as distinct tokens.
+//!
+//! This module provides an adapter that reports counted ANSI escape sequences by their byte
+//! span instead of as placeholder graphemes, so they can be sliced out of the input.
+
+use crate::{Grapheme, GraphemeIterator, Result, MAX_GRAPHEME_SIZE};
+
+/// Escape character that introduces an ANSI escape sequence
+const ESC: char = '\x1b';
+
+/// A grapheme cluster or an ANSI escape sequence.
+///
+/// Yielded by [`Tokens`].
+#[derive(Debug, Clone, Copy)]
+pub enum Token<const N: usize = MAX_GRAPHEME_SIZE> {
+    /// A grapheme cluster
+    Grapheme(Grapheme<N>),
+    /// An ANSI escape sequence
+    Ansi {
+        /// Byte offset of the sequence in the source text
+        offset: usize,
+        /// Length in bytes of the sequence in the source text
+        len: usize,
+    },
+}
+
+impl<const N: usize> Token<N> {
+    /// Converts a grapheme from the underlying iterator into a token.
+    ///
+    /// Escape characters always start an ANSI sequence and never join a cluster, so a
+    /// grapheme consisting of one is the placeholder for a counted sequence.
+    #[inline]
+    fn from_grapheme(grapheme: Grapheme<N>) -> Self {
+        if grapheme.as_chars() == [ESC] {
+            Self::Ansi {
+                offset: grapheme.byte_offset(),
+                len: grapheme.byte_len(),
+            }
+        } else {
+            Self::Grapheme(grapheme)
+        }
+    }
+}
+
+/// Iterator adapter that yields ANSI escape sequences as [`Token::Ansi`].
+///
+/// Created by [`GraphemeIterator::tokens`]. Grapheme clusters are yielded unchanged as
+/// [`Token::Grapheme`], while ANSI sequences are yielded with their byte span in the source
+/// text. Sequences are only yielded if the underlying iterator counts them, so an iterator
+/// created with `count_ansi` set to `false` still skips them.
+///
+/// Errors from the underlying iterator are passed through.
+///
+/// # Examples
+///
+/// ```
+/// use graphmemes::{GraphemeIterator, Result, Token};
+///
+/// # fn main() -> Result<()> {
+/// let text = "\x1b[1mhi";
+/// let tokens: Vec<_> = GraphemeIterator::new(text, true).tokens().collect::<Result<_>>()?;
+/// assert!(matches!(tokens[0], Token::Ansi { offset: 0, len: 4 }));
+/// assert!(matches!(tokens[1], Token::Grapheme(g) if g == 'h'));
+/// # Ok(())
+/// # }
+/// ```
+pub struct Tokens<'a, const N: usize = MAX_GRAPHEME_SIZE> {
+    /// The underlying grapheme iterator
+    iter: GraphemeIterator<'a, N>,
+}
+
+impl<'a, const N: usize> Iterator for Tokens<'a, N> {
+    type Item = Result<Token<N>>;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        self.iter.next().map(|item| item.map(Token::from_grapheme))
+    }
+}
+
+impl<'a, const N: usize> DoubleEndedIterator for Tokens<'a, N> {
+    #[inline]
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.iter
+            .next_back()
+            .map(|item| item.map(Token::from_grapheme))
+    }
+}
+
+impl<'a, const N: usize> GraphemeIterator<'a, N> {
+    /// Yields counted ANSI escape sequences by their byte span instead of as graphemes.
+    ///
+    /// See [`Tokens`] for details.
+    #[inline]
+    pub fn tokens(self) -> Tokens<'a, N> {
+        Tokens { iter: self }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use heapless::Vec;
+
+    /// Collects the tokens of `input`
+    fn tokens(input: &str, count_ansi: bool) -> Vec<Token, 16> {
+        let iter = GraphemeIterator::new(input, count_ansi).tokens();
+        iter.map(|t| t.unwrap()).collect()
+    }
+
+    #[test]
+    fn test_ansi_spans() {
+        let text = "\x1b[31mX\x1b[0m";
+        let tokens = tokens(text, true);
+        assert_eq!(tokens.len(), 3);
+
+        let Token::Ansi { offset, len } = tokens[0] else {
+            panic!("expected an ANSI sequence, got {:?}", tokens[0]);
+        };
+        assert_eq!(&text[offset..offset + len], "\x1b[31m");
+
+        assert!(matches!(tokens[1], Token::Grapheme(g) if g == 'X'));
+
+        let Token::Ansi { offset, len } = tokens[2] else {
+            panic!("expected an ANSI sequence, got {:?}", tokens[2]);
+        };
+        assert_eq!(&text[offset..offset + len], "\x1b[0m");
+    }
+
+    #[test]
+    fn test_uncounted_ansi() {
+        let tokens = tokens("\x1b[31mX\x1b[0m", false);
+        assert_eq!(tokens.len(), 1);
+        assert!(matches!(tokens[0], Token::Grapheme(g) if g == 'X'));
+    }
+
+    #[test]
+    fn test_osc_span() {
+        let text = "a\x1b]0;title\x07b";
+        let tokens = tokens(text, true);
+        assert_eq!(tokens.len(), 3);
+        assert!(matches!(tokens[1], Token::Ansi { offset: 1, len: 10 }));
+    }
+
+    #[test]
+    fn test_reverse() {
+        let text = "\x1b[31mX\x1b[0m";
+        let mut iter = GraphemeIterator::new(text, true).tokens();
+        assert!(matches!(
+            iter.next_back(),
+            Some(Ok(Token::Ansi { offset: 6, len: 4 }))
+        ));
+        assert!(matches!(iter.next_back(), Some(Ok(Token::Grapheme(_)))));
+        assert!(matches!(
+            iter.next(),
+            Some(Ok(Token::Ansi { offset: 0, .. }))
+        ));
+        assert!(iter.next().is_none());
+    }
+}
@@ -0,0 +1,125 @@
+//! Locating the visible text between ANSI escape sequences.
+
+use core::iter::FusedIterator;
+
+use crate::GraphemeIterator;
+
+/// Returns an iterator over the byte spans of `text` outside ANSI escape sequences.
+///
+/// See [`VisibleSpans`] for details.
+///
+/// # Examples
+///
+/// ```
+/// use graphmemes::visible_spans;
+///
+/// let text = "\x1b[31mred\x1b[0m text";
+/// let spans: Vec<_> = visible_spans(text).collect();
+/// assert_eq!(spans, [(5, 3), (12, 5)]);
+/// ```
+#[inline]
+pub fn visible_spans(text: &str) -> VisibleSpans<'_> {
+    VisibleSpans {
+        iter: GraphemeIterator::new(text, false).lossy(),
+        pending: None,
+    }
+}
+
+/// Iterator over the byte spans of a text outside ANSI escape sequences.
+///
+/// Created by [`visible_spans`]. Each item is an `(offset, len)` pair, and consecutive
+/// clusters are merged into a single span, so spans are only split by escape sequences.
+/// Concatenating the spans therefore reconstructs the text with its escape sequences
+/// removed. The part of the text the iterator reports as a malformed escape sequence is not
+/// part of any span, while clusters too long for the grapheme buffer are.
+///
+/// Spans are yielded in order and are never empty.
+pub struct VisibleSpans<'a> {
+    /// The underlying lossy grapheme iterator, skipping ANSI sequences
+    iter: GraphemeIterator<'a>,
+    /// Span of the clusters read so far but not yet yielded
+    pending: Option<(usize, usize)>,
+}
+
+impl<'a> Iterator for VisibleSpans<'a> {
+    type Item = (usize, usize);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        for item in self.iter.by_ref() {
+            // Lossy mode splits overlong clusters instead of failing, so errors only come
+            // from malformed escape sequences
+            let Ok(grapheme) = item else { continue };
+            let (offset, len) = (grapheme.byte_offset(), grapheme.byte_len());
+
+            match self.pending {
+                Some((start, pending_len)) if start + pending_len == offset => {
+                    self.pending = Some((start, pending_len + len));
+                }
+                pending => {
+                    self.pending = Some((offset, len));
+                    if pending.is_some() {
+                        return pending;
+                    }
+                }
+            }
+        }
+
+        self.pending.take()
+    }
+}
+
+impl<'a> FusedIterator for VisibleSpans<'a> {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use heapless::{String, Vec};
+
+    /// Collects the visible spans of `text`
+    fn spans(text: &str) -> Vec<(usize, usize), 16> {
+        visible_spans(text).collect()
+    }
+
+    /// Concatenates the visible spans of `text`
+    fn visible(text: &str) -> String<64> {
+        let mut out = String::new();
+        for (offset, len) in visible_spans(text) {
+            out.push_str(&text[offset..offset + len]).unwrap();
+        }
+        out
+    }
+
+    #[test]
+    fn test_colored_text() {
+        let text = "\x1b[31mred\x1b[0m text";
+        let spans = spans(text);
+        assert_eq!(spans, [(5, 3), (12, 5)]);
+        assert_eq!(&text[5..8], "red");
+        assert_eq!(&text[12..17], " text");
+        assert_eq!(visible(text), "red text");
+    }
+
+    #[test]
+    fn test_plain_text() {
+        assert_eq!(spans("hello 👋🏽"), [(0, 14)]);
+        assert_eq!(spans(""), []);
+        assert_eq!(spans("\x1b[1m\x1b[0m"), []);
+    }
+
+    #[test]
+    fn test_adjacent_sequences() {
+        let text = "a\x1b[1m\x1b[4mb\x1b[0m";
+        assert_eq!(spans(text), [(0, 1), (9, 1)]);
+        assert_eq!(visible(text), "ab");
+    }
+
+    #[test]
+    fn test_errors() {
+        // The escape character starting a malformed sequence is dropped
+        assert_eq!(visible("ab\x1b\u{1234}c"), "ab\u{1234}c");
+
+        // Overlong clusters are still visible text
+        let text = "a\x1b[1mb\u{0301}\u{0301}\u{0301}\u{0301}\u{0301}\u{0301}\u{0301}\u{0301}c";
+        assert_eq!(spans(text), [(0, 1), (5, text.len() - 5)]);
+    }
+}
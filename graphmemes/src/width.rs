@@ -0,0 +1,499 @@
+//! Display width of grapheme clusters.
+//!
+//! This module computes how many terminal columns a grapheme cluster occupies, following
+//! Unicode Standard Annex #11 (East Asian Width) the way most terminals interpret it:
+//! - Wide and Fullwidth characters occupy two columns
+//! - Combining marks, joiners and other zero-width characters occupy none
+//! - Control characters occupy none
+//! - Everything else occupies one column
+//!
+//! Ambiguous-width characters (e.g. `§`, Greek and Cyrillic letters, box drawing) are
+//! rendered as narrow by most terminals, but as wide in legacy CJK contexts, so the caller
+//! chooses how they are treated.
+//!
+//! Tables are sorted, non-overlapping inclusive ranges searched with a binary search, so
+//! lookups stay allocation-free and cheap on `no_std` targets.
+
+use core::cmp::Ordering;
+
+use crate::{boundary, Grapheme};
+
+/// Emoji presentation selector, forces a two-column emoji rendering
+const EMOJI_PRESENTATION: char = '\u{FE0F}';
+/// Text presentation selector, forces a single-column text rendering
+const TEXT_PRESENTATION: char = '\u{FE0E}';
+
+/// Options for computing the display width of grapheme clusters.
+///
+/// The default matches most terminals, rendering ambiguous-width characters as narrow.
+///
+/// # Examples
+///
+/// ```
+/// use graphmemes::{GraphemeIterator, Result, WidthConfig};
+///
+/// # fn main() -> Result<()> {
+/// let cjk = WidthConfig { ambiguous_wide: true };
+/// let grapheme = GraphemeIterator::new("§", false).next().unwrap()?;
+/// assert_eq!(grapheme.width_with(WidthConfig::default()), 1);
+/// assert_eq!(grapheme.width_with(cjk), 2);
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct WidthConfig {
+    /// Whether ambiguous-width characters occupy two columns, as in legacy CJK terminals
+    pub ambiguous_wide: bool,
+}
+
+/// Ranges of characters with an East Asian Width of Wide or Fullwidth.
+static WIDE: &[(char, char)] = &[
+    ('\u{1100}', '\u{115F}'),   // Hangul Jamo initial consonants
+    ('\u{231A}', '\u{231B}'),   // Watch, hourglass
+    ('\u{2329}', '\u{232A}'),   // Angle brackets
+    ('\u{23E9}', '\u{23EC}'),   // Media controls
+    ('\u{23F0}', '\u{23F0}'),   // Alarm clock
+    ('\u{23F3}', '\u{23F3}'),   // Hourglass with flowing sand
+    ('\u{25FD}', '\u{25FE}'),   // Medium small squares
+    ('\u{2614}', '\u{2615}'),   // Umbrella with rain, hot beverage
+    ('\u{2648}', '\u{2653}'),   // Zodiac signs
+    ('\u{267F}', '\u{267F}'),   // Wheelchair symbol
+    ('\u{2693}', '\u{2693}'),   // Anchor
+    ('\u{26A1}', '\u{26A1}'),   // High voltage
+    ('\u{26AA}', '\u{26AB}'),   // Medium circles
+    ('\u{26BD}', '\u{26BE}'),   // Soccer ball, baseball
+    ('\u{26C4}', '\u{26C5}'),   // Snowman, sun behind cloud
+    ('\u{26CE}', '\u{26CE}'),   // Ophiuchus
+    ('\u{26D4}', '\u{26D4}'),   // No entry
+    ('\u{26EA}', '\u{26EA}'),   // Church
+    ('\u{26F2}', '\u{26F3}'),   // Fountain, flag in hole
+    ('\u{26F5}', '\u{26F5}'),   // Sailboat
+    ('\u{26FA}', '\u{26FA}'),   // Tent
+    ('\u{26FD}', '\u{26FD}'),   // Fuel pump
+    ('\u{2705}', '\u{2705}'),   // Check mark button
+    ('\u{270A}', '\u{270B}'),   // Raised fist, raised hand
+    ('\u{2728}', '\u{2728}'),   // Sparkles
+    ('\u{274C}', '\u{274C}'),   // Cross mark
+    ('\u{274E}', '\u{274E}'),   // Cross mark button
+    ('\u{2753}', '\u{2755}'),   // Question and exclamation marks
+    ('\u{2757}', '\u{2757}'),   // Heavy exclamation mark
+    ('\u{2795}', '\u{2797}'),   // Heavy plus, minus, division
+    ('\u{27B0}', '\u{27B0}'),   // Curly loop
+    ('\u{27BF}', '\u{27BF}'),   // Double curly loop
+    ('\u{2B1B}', '\u{2B1C}'),   // Large squares
+    ('\u{2B50}', '\u{2B50}'),   // Star
+    ('\u{2B55}', '\u{2B55}'),   // Heavy large circle
+    ('\u{2E80}', '\u{303E}'),   // CJK radicals, Kangxi, CJK symbols and punctuation
+    ('\u{3041}', '\u{33FF}'),   // Hiragana, Katakana, Bopomofo, CJK compatibility
+    ('\u{3400}', '\u{4DBF}'),   // CJK Unified Ideographs Extension A
+    ('\u{4E00}', '\u{9FFF}'),   // CJK Unified Ideographs
+    ('\u{A000}', '\u{A4CF}'),   // Yi syllables and radicals
+    ('\u{A960}', '\u{A97F}'),   // Hangul Jamo Extended-A
+    ('\u{AC00}', '\u{D7A3}'),   // Hangul syllables
+    ('\u{F900}', '\u{FAFF}'),   // CJK Compatibility Ideographs
+    ('\u{FE10}', '\u{FE19}'),   // Vertical forms
+    ('\u{FE30}', '\u{FE6F}'),   // CJK compatibility forms, small form variants
+    ('\u{FF00}', '\u{FF60}'),   // Fullwidth forms
+    ('\u{FFE0}', '\u{FFE6}'),   // Fullwidth signs
+    ('\u{16FE0}', '\u{16FE4}'), // Ideographic symbols and punctuation
+    ('\u{17000}', '\u{18CFF}'), // Tangut, Khitan
+    ('\u{1B000}', '\u{1B2FF}'), // Kana supplement and extensions, Nushu
+    ('\u{1F004}', '\u{1F004}'), // Mahjong tile red dragon
+    ('\u{1F0CF}', '\u{1F0CF}'), // Playing card black joker
+    ('\u{1F18E}', '\u{1F18E}'), // Negative squared AB
+    ('\u{1F191}', '\u{1F19A}'), // Squared CL through VS
+    ('\u{1F200}', '\u{1F202}'), // Squared hiragana and katakana
+    ('\u{1F210}', '\u{1F23B}'), // Squared CJK ideographs
+    ('\u{1F240}', '\u{1F248}'), // Tortoise shell bracketed ideographs
+    ('\u{1F250}', '\u{1F251}'), // Circled ideographs
+    ('\u{1F260}', '\u{1F265}'), // Rounded symbols
+    ('\u{1F300}', '\u{1F320}'), // Weather and landscape emoji
+    ('\u{1F32D}', '\u{1F335}'), // Food and plants
+    ('\u{1F337}', '\u{1F37C}'), // Plants, food and drink
+    ('\u{1F37E}', '\u{1F393}'), // Drink and celebration
+    ('\u{1F3A0}', '\u{1F3CA}'), // Activities
+    ('\u{1F3CF}', '\u{1F3D3}'), // Sports
+    ('\u{1F3E0}', '\u{1F3F0}'), // Buildings
+    ('\u{1F3F4}', '\u{1F3F4}'), // Black flag
+    ('\u{1F3F8}', '\u{1F43E}'), // Sports, skin tones, animals
+    ('\u{1F440}', '\u{1F440}'), // Eyes
+    ('\u{1F442}', '\u{1F4FC}'), // People, body parts, objects
+    ('\u{1F4FF}', '\u{1F53D}'), // Objects and symbols
+    ('\u{1F54B}', '\u{1F54E}'), // Religious symbols
+    ('\u{1F550}', '\u{1F567}'), // Clock faces
+    ('\u{1F57A}', '\u{1F57A}'), // Man dancing
+    ('\u{1F595}', '\u{1F596}'), // Hand gestures
+    ('\u{1F5A4}', '\u{1F5A4}'), // Black heart
+    ('\u{1F5FB}', '\u{1F64F}'), // Landmarks, emoticons
+    ('\u{1F680}', '\u{1F6C5}'), // Transport and map symbols
+    ('\u{1F6CC}', '\u{1F6CC}'), // Sleeping accommodation
+    ('\u{1F6D0}', '\u{1F6D2}'), // Place of worship, shopping trolley
+    ('\u{1F6D5}', '\u{1F6D7}'), // Hindu temple, hut, elevator
+    ('\u{1F6DC}', '\u{1F6DF}'), // Wireless, playground slide, wheel, ring buoy
+    ('\u{1F6EB}', '\u{1F6EC}'), // Airplane departure and arrival
+    ('\u{1F6F4}', '\u{1F6FC}'), // Scooters and vehicles
+    ('\u{1F7E0}', '\u{1F7EB}'), // Large colored circles and squares
+    ('\u{1F7F0}', '\u{1F7F0}'), // Heavy equals sign
+    ('\u{1F90C}', '\u{1F93A}'), // Supplemental symbols and pictographs
+    ('\u{1F93C}', '\u{1F945}'), // Supplemental symbols and pictographs
+    ('\u{1F947}', '\u{1F9FF}'), // Supplemental symbols and pictographs
+    ('\u{1FA70}', '\u{1FAFF}'), // Symbols and pictographs extended-A
+    ('\u{20000}', '\u{2FFFD}'), // CJK Unified Ideographs Extensions B-F, supplement
+    ('\u{30000}', '\u{3FFFD}'), // CJK Unified Ideographs Extensions G-H
+];
+
+/// Ranges of characters with an East Asian Width of Ambiguous.
+static AMBIGUOUS: &[(char, char)] = &[
+    ('\u{00A1}', '\u{00A1}'),     // Inverted exclamation mark
+    ('\u{00A4}', '\u{00A4}'),     // Currency sign
+    ('\u{00A7}', '\u{00A8}'),     // Section sign, diaeresis
+    ('\u{00AA}', '\u{00AA}'),     // Feminine ordinal indicator
+    ('\u{00AD}', '\u{00AE}'),     // Soft hyphen, registered sign
+    ('\u{00B0}', '\u{00B4}'),     // Degree sign through acute accent
+    ('\u{00B6}', '\u{00BA}'),     // Pilcrow through masculine ordinal indicator
+    ('\u{00BC}', '\u{00BF}'),     // Vulgar fractions, inverted question mark
+    ('\u{00C6}', '\u{00C6}'),     // Latin capital AE
+    ('\u{00D0}', '\u{00D0}'),     // Latin capital eth
+    ('\u{00D7}', '\u{00D8}'),     // Multiplication sign, O with stroke
+    ('\u{00DE}', '\u{00E1}'),     // Latin thorn through a with acute
+    ('\u{00E6}', '\u{00E6}'),     // Latin small ae
+    ('\u{00E8}', '\u{00EA}'),     // Latin small e with grave through circumflex
+    ('\u{00EC}', '\u{00ED}'),     // Latin small i with grave and acute
+    ('\u{00F0}', '\u{00F0}'),     // Latin small eth
+    ('\u{00F2}', '\u{00F3}'),     // Latin small o with grave and acute
+    ('\u{00F7}', '\u{00FA}'),     // Division sign through u with acute
+    ('\u{00FC}', '\u{00FC}'),     // Latin small u with diaeresis
+    ('\u{00FE}', '\u{00FE}'),     // Latin small thorn
+    ('\u{0101}', '\u{0101}'),     // Latin small a with macron
+    ('\u{0111}', '\u{0111}'),     // Latin small d with stroke
+    ('\u{0113}', '\u{0113}'),     // Latin small e with macron
+    ('\u{011B}', '\u{011B}'),     // Latin small e with caron
+    ('\u{0126}', '\u{0127}'),     // Latin H with stroke
+    ('\u{012B}', '\u{012B}'),     // Latin small i with macron
+    ('\u{0131}', '\u{0133}'),     // Dotless i, ligature IJ
+    ('\u{0138}', '\u{0138}'),     // Latin small kra
+    ('\u{013F}', '\u{0142}'),     // Latin L with middle dot and stroke
+    ('\u{0144}', '\u{0144}'),     // Latin small n with acute
+    ('\u{0148}', '\u{014B}'),     // Latin small n with caron through eng
+    ('\u{014D}', '\u{014D}'),     // Latin small o with macron
+    ('\u{0152}', '\u{0153}'),     // Ligature OE
+    ('\u{0166}', '\u{0167}'),     // Latin T with stroke
+    ('\u{016B}', '\u{016B}'),     // Latin small u with macron
+    ('\u{01CE}', '\u{01CE}'),     // Latin small a with caron
+    ('\u{01D0}', '\u{01D0}'),     // Latin small i with caron
+    ('\u{01D2}', '\u{01D2}'),     // Latin small o with caron
+    ('\u{01D4}', '\u{01D4}'),     // Latin small u with caron
+    ('\u{01D6}', '\u{01D6}'),     // Latin small u with diaeresis and macron
+    ('\u{01D8}', '\u{01D8}'),     // Latin small u with diaeresis and acute
+    ('\u{01DA}', '\u{01DA}'),     // Latin small u with diaeresis and caron
+    ('\u{01DC}', '\u{01DC}'),     // Latin small u with diaeresis and grave
+    ('\u{0251}', '\u{0251}'),     // Latin small alpha
+    ('\u{0261}', '\u{0261}'),     // Latin small script g
+    ('\u{02C4}', '\u{02C4}'),     // Modifier letter up arrowhead
+    ('\u{02C7}', '\u{02C7}'),     // Caron
+    ('\u{02C9}', '\u{02CB}'),     // Modifier letter macron, acute, grave
+    ('\u{02CD}', '\u{02CD}'),     // Modifier letter low macron
+    ('\u{02D0}', '\u{02D0}'),     // Modifier letter triangular colon
+    ('\u{02D8}', '\u{02DB}'),     // Breve through ogonek
+    ('\u{02DD}', '\u{02DD}'),     // Double acute accent
+    ('\u{02DF}', '\u{02DF}'),     // Modifier letter cross accent
+    ('\u{0391}', '\u{03A1}'),     // Greek capital alpha through rho
+    ('\u{03A3}', '\u{03A9}'),     // Greek capital sigma through omega
+    ('\u{03B1}', '\u{03C1}'),     // Greek small alpha through rho
+    ('\u{03C3}', '\u{03C9}'),     // Greek small sigma through omega
+    ('\u{0401}', '\u{0401}'),     // Cyrillic capital IO
+    ('\u{0410}', '\u{044F}'),     // Cyrillic basic letters
+    ('\u{0451}', '\u{0451}'),     // Cyrillic small io
+    ('\u{2010}', '\u{2010}'),     // Hyphen
+    ('\u{2013}', '\u{2016}'),     // Dashes, double vertical line
+    ('\u{2018}', '\u{2019}'),     // Single quotation marks
+    ('\u{201C}', '\u{201D}'),     // Double quotation marks
+    ('\u{2020}', '\u{2022}'),     // Daggers, bullet
+    ('\u{2024}', '\u{2027}'),     // Leaders, hyphenation point
+    ('\u{2030}', '\u{2030}'),     // Per mille sign
+    ('\u{2032}', '\u{2033}'),     // Prime, double prime
+    ('\u{2035}', '\u{2035}'),     // Reversed prime
+    ('\u{203B}', '\u{203B}'),     // Reference mark
+    ('\u{203E}', '\u{203E}'),     // Overline
+    ('\u{2074}', '\u{2074}'),     // Superscript four
+    ('\u{207F}', '\u{207F}'),     // Superscript latin small n
+    ('\u{2081}', '\u{2084}'),     // Subscript one through four
+    ('\u{20AC}', '\u{20AC}'),     // Euro sign
+    ('\u{2103}', '\u{2103}'),     // Degree Celsius
+    ('\u{2105}', '\u{2105}'),     // Care of
+    ('\u{2109}', '\u{2109}'),     // Degree Fahrenheit
+    ('\u{2113}', '\u{2113}'),     // Script small l
+    ('\u{2116}', '\u{2116}'),     // Numero sign
+    ('\u{2121}', '\u{2122}'),     // Telephone sign, trade mark
+    ('\u{2126}', '\u{2126}'),     // Ohm sign
+    ('\u{212B}', '\u{212B}'),     // Angstrom sign
+    ('\u{2153}', '\u{2154}'),     // Vulgar fractions thirds
+    ('\u{215B}', '\u{215E}'),     // Vulgar fractions eighths
+    ('\u{2160}', '\u{216B}'),     // Roman numerals
+    ('\u{2170}', '\u{2179}'),     // Small roman numerals
+    ('\u{2189}', '\u{2189}'),     // Vulgar fraction zero thirds
+    ('\u{2190}', '\u{2199}'),     // Arrows
+    ('\u{21B8}', '\u{21B9}'),     // Arrows
+    ('\u{21D2}', '\u{21D2}'),     // Rightwards double arrow
+    ('\u{21D4}', '\u{21D4}'),     // Left right double arrow
+    ('\u{21E7}', '\u{21E7}'),     // Upwards white arrow
+    ('\u{2200}', '\u{2200}'),     // For all
+    ('\u{2202}', '\u{2203}'),     // Partial differential, there exists
+    ('\u{2207}', '\u{2208}'),     // Nabla, element of
+    ('\u{220B}', '\u{220B}'),     // Contains as member
+    ('\u{220F}', '\u{220F}'),     // N-ary product
+    ('\u{2211}', '\u{2211}'),     // N-ary summation
+    ('\u{2215}', '\u{2215}'),     // Division slash
+    ('\u{221A}', '\u{221A}'),     // Square root
+    ('\u{221D}', '\u{2220}'),     // Proportional to through angle
+    ('\u{2223}', '\u{2223}'),     // Divides
+    ('\u{2225}', '\u{2225}'),     // Parallel to
+    ('\u{2227}', '\u{222C}'),     // Logical and through double integral
+    ('\u{222E}', '\u{222E}'),     // Contour integral
+    ('\u{2234}', '\u{2237}'),     // Therefore through proportion
+    ('\u{223C}', '\u{223D}'),     // Tilde operators
+    ('\u{2248}', '\u{2248}'),     // Almost equal to
+    ('\u{224C}', '\u{224C}'),     // All equal to
+    ('\u{2252}', '\u{2252}'),     // Approximately equal to or the image of
+    ('\u{2260}', '\u{2261}'),     // Not equal to, identical to
+    ('\u{2264}', '\u{2267}'),     // Less and greater than or equal to
+    ('\u{226A}', '\u{226B}'),     // Much less and greater than
+    ('\u{226E}', '\u{226F}'),     // Not less and greater than
+    ('\u{2282}', '\u{2283}'),     // Subset, superset
+    ('\u{2286}', '\u{2287}'),     // Subset or equal, superset or equal
+    ('\u{2295}', '\u{2295}'),     // Circled plus
+    ('\u{2299}', '\u{2299}'),     // Circled dot operator
+    ('\u{22A5}', '\u{22A5}'),     // Up tack
+    ('\u{22BF}', '\u{22BF}'),     // Right triangle
+    ('\u{2312}', '\u{2312}'),     // Arc
+    ('\u{2460}', '\u{24E9}'),     // Enclosed alphanumerics
+    ('\u{24EB}', '\u{254B}'),     // Enclosed alphanumerics, box drawing
+    ('\u{2550}', '\u{2573}'),     // Box drawing
+    ('\u{2580}', '\u{258F}'),     // Block elements
+    ('\u{2592}', '\u{2595}'),     // Shades and block elements
+    ('\u{25A0}', '\u{25A1}'),     // Black and white squares
+    ('\u{25A3}', '\u{25A9}'),     // Geometric shapes
+    ('\u{25B2}', '\u{25B3}'),     // Up-pointing triangles
+    ('\u{25B6}', '\u{25B7}'),     // Right-pointing triangles
+    ('\u{25BC}', '\u{25BD}'),     // Down-pointing triangles
+    ('\u{25C0}', '\u{25C1}'),     // Left-pointing triangles
+    ('\u{25C6}', '\u{25C8}'),     // Diamonds
+    ('\u{25CB}', '\u{25CB}'),     // White circle
+    ('\u{25CE}', '\u{25D1}'),     // Bullseye, circles
+    ('\u{25E2}', '\u{25E5}'),     // Triangles
+    ('\u{25EF}', '\u{25EF}'),     // Large circle
+    ('\u{2605}', '\u{2606}'),     // Stars
+    ('\u{2609}', '\u{2609}'),     // Sun
+    ('\u{260E}', '\u{260F}'),     // Telephones
+    ('\u{261C}', '\u{261C}'),     // White left pointing index
+    ('\u{261E}', '\u{261E}'),     // White right pointing index
+    ('\u{2640}', '\u{2640}'),     // Female sign
+    ('\u{2642}', '\u{2642}'),     // Male sign
+    ('\u{2660}', '\u{2661}'),     // Card suits
+    ('\u{2663}', '\u{2665}'),     // Card suits
+    ('\u{2667}', '\u{266A}'),     // Card suits, eighth note
+    ('\u{266C}', '\u{266D}'),     // Beamed sixteenth notes, flat sign
+    ('\u{266F}', '\u{266F}'),     // Sharp sign
+    ('\u{269E}', '\u{269F}'),     // Three lines converging
+    ('\u{26BF}', '\u{26BF}'),     // Squared key
+    ('\u{26C6}', '\u{26CD}'),     // Weather and traffic symbols
+    ('\u{26CF}', '\u{26D3}'),     // Pick through chains
+    ('\u{26D5}', '\u{26E1}'),     // Traffic and map symbols
+    ('\u{26E3}', '\u{26E3}'),     // Heavy circle with stroke and two dots above
+    ('\u{26E8}', '\u{26E9}'),     // Shield and shinto shrine
+    ('\u{26EB}', '\u{26F1}'),     // Map symbols
+    ('\u{26F4}', '\u{26F4}'),     // Ferry
+    ('\u{26F6}', '\u{26F9}'),     // Map symbols
+    ('\u{26FB}', '\u{26FC}'),     // Map symbols
+    ('\u{26FE}', '\u{26FF}'),     // Cup on black square, white flag with horizontal middle stripe
+    ('\u{273D}', '\u{273D}'),     // Heavy teardrop-spoked asterisk
+    ('\u{2776}', '\u{277F}'),     // Dingbat negative circled digits
+    ('\u{2B56}', '\u{2B59}'),     // Heavy ovals and circled saltire
+    ('\u{3248}', '\u{324F}'),     // Circled numbers on black squares
+    ('\u{E000}', '\u{F8FF}'),     // Private use area
+    ('\u{FFFD}', '\u{FFFD}'),     // Replacement character
+    ('\u{1F100}', '\u{1F10A}'),   // Digit full stop and comma
+    ('\u{1F110}', '\u{1F12D}'),   // Parenthesized and circled latin letters
+    ('\u{1F130}', '\u{1F169}'),   // Squared and circled latin letters
+    ('\u{1F170}', '\u{1F18D}'),   // Negative squared latin letters
+    ('\u{1F18F}', '\u{1F190}'),   // Negative squared WC, squared DJ
+    ('\u{1F19B}', '\u{1F1AC}'),   // Squared latin letter combinations
+    ('\u{F0000}', '\u{FFFFD}'),   // Supplementary private use area A
+    ('\u{100000}', '\u{10FFFD}'), // Supplementary private use area B
+];
+
+/// Ranges of characters that occupy no columns of their own.
+///
+/// Covers combining marks, Hangul medial and final jamo, joiners and other format
+/// characters, variation selectors and tags.
+static ZERO_WIDTH: &[(char, char)] = &[
+    ('\u{0300}', '\u{036F}'),   // Combining diacritical marks
+    ('\u{0483}', '\u{0489}'),   // Cyrillic combining marks
+    ('\u{0591}', '\u{05BD}'),   // Hebrew points and accents
+    ('\u{05BF}', '\u{05BF}'),   // Hebrew point rafe
+    ('\u{05C1}', '\u{05C2}'),   // Hebrew points shin and sin dot
+    ('\u{05C4}', '\u{05C5}'),   // Hebrew marks upper and lower dot
+    ('\u{05C7}', '\u{05C7}'),   // Hebrew point qamats qatan
+    ('\u{0600}', '\u{0605}'),   // Arabic number signs
+    ('\u{0610}', '\u{061A}'),   // Arabic signs
+    ('\u{061C}', '\u{061C}'),   // Arabic letter mark
+    ('\u{064B}', '\u{065F}'),   // Arabic harakat
+    ('\u{0670}', '\u{0670}'),   // Arabic letter superscript alef
+    ('\u{06D6}', '\u{06DD}'),   // Arabic small high marks, end of ayah
+    ('\u{06DF}', '\u{06E4}'),   // Arabic small high marks
+    ('\u{06E7}', '\u{06E8}'),   // Arabic small high yeh and noon
+    ('\u{06EA}', '\u{06ED}'),   // Arabic empty centre stops
+    ('\u{070F}', '\u{070F}'),   // Syriac abbreviation mark
+    ('\u{0890}', '\u{0891}'),   // Arabic pound and piastre marks above
+    ('\u{08E2}', '\u{08E2}'),   // Arabic disputed end of ayah
+    ('\u{0900}', '\u{0902}'),   // Devanagari signs
+    ('\u{093A}', '\u{093A}'),   // Devanagari vowel sign oe
+    ('\u{093C}', '\u{093C}'),   // Devanagari sign nukta
+    ('\u{0941}', '\u{0948}'),   // Devanagari vowel signs
+    ('\u{094D}', '\u{094D}'),   // Devanagari sign virama
+    ('\u{0951}', '\u{0957}'),   // Devanagari stress signs
+    ('\u{0962}', '\u{0963}'),   // Devanagari vowel signs vocalic
+    ('\u{0981}', '\u{0981}'),   // Bengali sign candrabindu
+    ('\u{09BC}', '\u{09BC}'),   // Bengali sign nukta
+    ('\u{09C1}', '\u{09C4}'),   // Bengali vowel signs
+    ('\u{09CD}', '\u{09CD}'),   // Bengali sign virama
+    ('\u{0E31}', '\u{0E31}'),   // Thai character mai han-akat
+    ('\u{0E34}', '\u{0E3A}'),   // Thai vowel signs
+    ('\u{0E47}', '\u{0E4E}'),   // Thai tone marks
+    ('\u{1160}', '\u{11FF}'),   // Hangul Jamo medial vowels and final consonants
+    ('\u{1AB0}', '\u{1AFF}'),   // Combining diacritical marks extended
+    ('\u{1DC0}', '\u{1DFF}'),   // Combining diacritical marks supplement
+    ('\u{200B}', '\u{200F}'),   // Zero width space, joiners, direction marks
+    ('\u{202A}', '\u{202E}'),   // Bidirectional embeddings and overrides
+    ('\u{2060}', '\u{2064}'),   // Word joiner, invisible operators
+    ('\u{20D0}', '\u{20FF}'),   // Combining marks for symbols
+    ('\u{302A}', '\u{302D}'),   // Ideographic tone marks
+    ('\u{3099}', '\u{309A}'),   // Combining katakana-hiragana sound marks
+    ('\u{D7B0}', '\u{D7FF}'),   // Hangul Jamo Extended-B
+    ('\u{FE00}', '\u{FE0F}'),   // Variation selectors
+    ('\u{FE20}', '\u{FE2F}'),   // Combining half marks
+    ('\u{FEFF}', '\u{FEFF}'),   // Zero width no-break space
+    ('\u{E0000}', '\u{E0FFF}'), // Tags, variation selectors supplement
+];
+
+/// Returns whether a character falls within one of the ranges in a sorted table.
+#[inline]
+fn in_table(c: char, table: &[(char, char)]) -> bool {
+    table
+        .binary_search_by(|&(lo, hi)| {
+            if hi < c {
+                Ordering::Less
+            } else if lo > c {
+                Ordering::Greater
+            } else {
+                Ordering::Equal
+            }
+        })
+        .is_ok()
+}
+
+/// Returns the number of columns a single character occupies.
+///
+/// # Arguments
+///
+/// * `c` - The character to measure
+/// * `ambiguous_wide` - Whether ambiguous-width characters occupy two columns
+#[inline]
+pub(crate) fn char_width(c: char, ambiguous_wide: bool) -> usize {
+    match c {
+        // Printable ASCII fast path
+        ' '..='~' => 1,
+        c if c.is_control() => 0,
+        c if in_table(c, ZERO_WIDTH) => 0,
+        c if in_table(c, WIDE) => 2,
+        c if ambiguous_wide && in_table(c, AMBIGUOUS) => 2,
+        _ => 1,
+    }
+}
+
+/// Returns the number of columns a grapheme cluster occupies.
+///
+/// The cluster is as wide as its widest character, so zero-width marks never add columns
+/// and clusters made only of zero-width characters occupy none. Presentation selectors and
+/// flag pairs override the character widths, since terminals render them as a single
+/// emoji or text glyph.
+///
+/// # Arguments
+///
+/// * `chars` - The characters of the cluster
+/// * `ambiguous_wide` - Whether ambiguous-width characters occupy two columns
+pub(crate) fn cluster_width(chars: &[char], ambiguous_wide: bool) -> usize {
+    let width = chars
+        .iter()
+        .map(|&c| char_width(c, ambiguous_wide))
+        .max()
+        .unwrap_or(0);
+
+    match chars {
+        // Nothing visible to present
+        _ if width == 0 => 0,
+        // A pair of regional indicators renders as a flag
+        [a, b, ..]
+            if Grapheme::char_category(*a) == boundary::REGIONAL
+                && Grapheme::char_category(*b) == boundary::REGIONAL =>
+        {
+            2
+        }
+        _ if chars.contains(&EMOJI_PRESENTATION) => 2,
+        _ if chars.contains(&TEXT_PRESENTATION) => 1,
+        _ => width,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_tables_sorted() {
+        for table in [WIDE, AMBIGUOUS, ZERO_WIDTH] {
+            for pair in table.windows(2) {
+                assert!(pair[0].0 <= pair[0].1, "inverted range {:?}", pair[0]);
+                assert!(pair[0].1 < pair[1].0, "unsorted ranges {:?}", pair);
+            }
+        }
+    }
+
+    #[test]
+    fn test_char_width() {
+        assert_eq!(char_width('a', false), 1);
+        assert_eq!(char_width('世', false), 2);
+        assert_eq!(char_width('👋', false), 2);
+        assert_eq!(char_width('\u{0301}', false), 0);
+        assert_eq!(char_width('\u{200D}', false), 0);
+        assert_eq!(char_width('\t', false), 0);
+        assert_eq!(char_width('\x1b', false), 0);
+    }
+
+    #[test]
+    fn test_ambiguous_width() {
+        assert_eq!(char_width('§', false), 1);
+        assert_eq!(char_width('§', true), 2);
+        assert_eq!(char_width('α', true), 2);
+        // Unambiguous characters ignore the setting
+        assert_eq!(char_width('a', true), 1);
+        assert_eq!(char_width('世', true), 2);
+    }
+
+    #[test]
+    fn test_cluster_width() {
+        assert_eq!(cluster_width(&['a'], false), 1);
+        assert_eq!(cluster_width(&['e', '\u{0301}'], false), 1);
+        assert_eq!(cluster_width(&['\u{0301}'], false), 0);
+        assert_eq!(cluster_width(&['👨', '\u{200D}', '💻'], false), 2);
+        assert_eq!(cluster_width(&['👋', '🏽'], false), 2);
+        assert_eq!(
+            cluster_width(&['\u{1100}', '\u{1161}', '\u{11A8}'], false),
+            2
+        );
+        assert_eq!(cluster_width(&['🇺', '🇸'], false), 2);
+        assert_eq!(cluster_width(&['❤', '\u{FE0F}'], false), 2);
+        assert_eq!(cluster_width(&['⌚', '\u{FE0E}'], false), 1);
+        assert_eq!(cluster_width(&[], false), 0);
+    }
+}
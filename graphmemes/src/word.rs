@@ -0,0 +1,358 @@
+//! Word iteration following UAX #29 word boundary rules.
+//!
+//! This module provides an iterator over the words of a string, for tasks like search
+//! highlighting that need to find whole words. It implements the core word boundary rules:
+//! - Letters and numbers join into words (WB5, WB8–WB10)
+//! - Apostrophes, periods and similar punctuation join letters (WB6, WB7)
+//! - Commas, periods and similar punctuation join numbers (WB11, WB12)
+//! - Connector punctuation such as `_` joins words (WB13a, WB13b)
+//! - Katakana joins into words, while each ideograph is a word of its own (WB13, WB999)
+//! - Combining marks and joiners belong to the character before them (WB4)
+//!
+//! Everything else, such as whitespace and other punctuation, separates words.
+
+use crate::{boundary, Grapheme};
+
+/// Categories of characters that extend the character before them, per WB4
+const EXTENDING: u32 = boundary::EXTEND
+    | boundary::ZWJ
+    | boundary::SPACINGMARK
+    | boundary::EMOJI_MOD
+    | boundary::INCB_LINKER;
+
+/// Word boundary category of a character.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum WordClass {
+    /// Alphabetic characters, including Hangul
+    Letter,
+    /// Numeric characters
+    Numeric,
+    /// Katakana, which joins with itself
+    Katakana,
+    /// Ideographs and Hiragana, which form a word per character
+    Ideographic,
+    /// Connector punctuation, such as `_`
+    ExtendNumLet,
+    /// Punctuation that joins letters, such as `:`
+    MidLetter,
+    /// Punctuation that joins numbers, such as `,`
+    MidNum,
+    /// Punctuation that joins both letters and numbers, such as `'` and `.`
+    MidNumLet,
+    /// Anything else, such as whitespace
+    Other,
+}
+
+impl WordClass {
+    /// Determines the word boundary category of a character.
+    fn of(c: char) -> Self {
+        match c {
+            'a'..='z' | 'A'..='Z' => Self::Letter,
+            '0'..='9' => Self::Numeric,
+            '_' => Self::ExtendNumLet,
+            ':' => Self::MidLetter,
+            ',' | ';' => Self::MidNum,
+            '.' | '\'' => Self::MidNumLet,
+            c if c.is_ascii() => Self::Other,
+            c if is_katakana(c) => Self::Katakana,
+            c if is_ideographic(c) => Self::Ideographic,
+            // Narrow no-break space, ties and low lines
+            '\u{202F}' | '\u{203F}' | '\u{2040}' | '\u{2054}' | '\u{FE33}' | '\u{FE34}'
+            | '\u{FE4D}' | '\u{FE4E}' | '\u{FE4F}' | '\u{FF3F}' => Self::ExtendNumLet,
+            // Middle dots, Armenian abbreviation mark, Hebrew gershayim, hyphenation point
+            // and colons
+            '\u{00B7}' | '\u{0387}' | '\u{055F}' | '\u{05F4}' | '\u{2027}' | '\u{FE13}'
+            | '\u{FE55}' | '\u{FF1A}' => Self::MidLetter,
+            // Commas, semicolons, Armenian full stop, Arabic separators and fraction slash
+            '\u{037E}' | '\u{0589}' | '\u{060C}' | '\u{060D}' | '\u{066C}' | '\u{07F8}'
+            | '\u{2044}' | '\u{FE10}' | '\u{FE14}' | '\u{FE50}' | '\u{FE54}' | '\u{FF0C}'
+            | '\u{FF1B}' => Self::MidNum,
+            // Single quotation marks, one dot leader, full stops and apostrophes
+            '\u{2018}' | '\u{2019}' | '\u{2024}' | '\u{FE52}' | '\u{FF07}' | '\u{FF0E}' => {
+                Self::MidNumLet
+            }
+            c if c.is_numeric() => Self::Numeric,
+            c if c.is_alphabetic() => Self::Letter,
+            _ => Self::Other,
+        }
+    }
+
+    /// Returns whether this category makes a segment a word.
+    #[inline]
+    fn is_word(self) -> bool {
+        matches!(
+            self,
+            Self::Letter | Self::Numeric | Self::Katakana | Self::Ideographic
+        )
+    }
+
+    /// Returns whether no word boundary lies between characters of these categories.
+    ///
+    /// Rules that need to look past the next character are handled by
+    /// [`joins_across`](Self::joins_across).
+    #[inline]
+    fn joins(self, next: Self) -> bool {
+        use WordClass::*;
+        matches!(
+            (self, next),
+            (Letter | Numeric, Letter | Numeric)
+                | (Katakana, Katakana)
+                | (Letter | Numeric | Katakana | ExtendNumLet, ExtendNumLet)
+                | (ExtendNumLet, Letter | Numeric | Katakana)
+        )
+    }
+
+    /// Returns whether the punctuation `mid` joins characters of these categories around it.
+    #[inline]
+    fn joins_across(self, mid: Self, next: Self) -> bool {
+        use WordClass::*;
+        matches!(
+            (self, mid, next),
+            (Letter, MidLetter | MidNumLet, Letter) | (Numeric, MidNum | MidNumLet, Numeric)
+        )
+    }
+}
+
+/// Determines if a non-ASCII character is Katakana.
+#[inline]
+fn is_katakana(c: char) -> bool {
+    matches!(c,
+        '\u{3031}'..='\u{3035}' |  // Vertical kana repeat marks
+        '\u{309B}'..='\u{309C}' |  // Kana voiced sound marks
+        '\u{30A0}'..='\u{30FA}' |  // Katakana
+        '\u{30FC}'..='\u{30FF}' |  // Prolonged sound mark, iteration marks, digraph
+        '\u{31F0}'..='\u{31FF}' |  // Katakana phonetic extensions
+        '\u{32D0}'..='\u{32FE}' |  // Circled Katakana
+        '\u{3300}'..='\u{3357}' |  // Squared Katakana words
+        '\u{FF66}'..='\u{FF9D}' |  // Halfwidth Katakana
+        '\u{1B000}'                // Katakana letter archaic E
+    )
+}
+
+/// Determines if a non-ASCII character is an ideograph or Hiragana.
+#[inline]
+fn is_ideographic(c: char) -> bool {
+    matches!(c,
+        '\u{3005}'..='\u{3007}' |    // Ideographic iteration mark, closing mark, number zero
+        '\u{3021}'..='\u{3029}' |    // Hangzhou numerals
+        '\u{3038}'..='\u{303B}' |    // Hangzhou numerals, vertical ideographic iteration mark
+        '\u{3041}'..='\u{3096}' |    // Hiragana
+        '\u{309D}'..='\u{309F}' |    // Hiragana iteration marks, digraph
+        '\u{3400}'..='\u{4DBF}' |    // CJK Unified Ideographs Extension A
+        '\u{4E00}'..='\u{9FFF}' |    // CJK Unified Ideographs
+        '\u{F900}'..='\u{FAFF}' |    // CJK Compatibility Ideographs
+        '\u{1B001}'..='\u{1B11F}' |  // Kana Supplement and Extended-A Hiragana
+        '\u{20000}'..='\u{2FFFF}' |  // Supplementary Ideographic Plane
+        '\u{30000}'..='\u{3FFFF}'    // Tertiary Ideographic Plane
+    )
+}
+
+/// A word found by [`WordIterator`].
+///
+/// Refers to the word's span in the source text.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Word<'a> {
+    /// The text of the word
+    text: &'a str,
+    /// Byte offset of the word in the source text
+    offset: usize,
+}
+
+impl<'a> Word<'a> {
+    /// Returns the text of the word.
+    #[inline]
+    pub fn as_str(&self) -> &'a str {
+        self.text
+    }
+
+    /// Returns the byte offset of the word in the source text.
+    #[inline]
+    pub fn byte_offset(&self) -> usize {
+        self.offset
+    }
+
+    /// Returns the length in bytes of the word.
+    #[inline]
+    pub fn byte_len(&self) -> usize {
+        self.text.len()
+    }
+}
+
+/// Iterator over the words of a string.
+///
+/// Splits the text at UAX #29 word boundaries and yields the segments containing letters,
+/// numbers or ideographs. The core word boundary rules are implemented: letters, numbers,
+/// katakana and connector punctuation join into words, apostrophes and periods join letters,
+/// commas and periods join numbers, combining marks stay with the character before them, and
+/// each ideograph is a word of its own.
+/// Whitespace and punctuation between words are skipped. Like [`GraphemeIterator`], it
+/// makes no allocations.
+///
+/// [`GraphemeIterator`]: crate::GraphemeIterator
+///
+/// # Examples
+///
+/// ```
+/// use graphmemes::WordIterator;
+///
+/// let words: Vec<_> = WordIterator::new("Don't panic, it's 3.14!")
+///     .map(|w| w.as_str())
+///     .collect();
+/// assert_eq!(words, ["Don't", "panic", "it's", "3.14"]);
+/// ```
+#[derive(Debug, Clone)]
+pub struct WordIterator<'a> {
+    /// The text being segmented
+    text: &'a str,
+    /// Byte offset of the next segment
+    pos: usize,
+}
+
+impl<'a> WordIterator<'a> {
+    /// Creates a new word iterator over `text`.
+    ///
+    /// # Arguments
+    ///
+    /// * `text` - The text to split into words
+    #[inline]
+    pub fn new(text: &'a str) -> Self {
+        Self { text, pos: 0 }
+    }
+
+    /// Returns the offset just past the character at `pos` and any characters extending it.
+    #[inline]
+    fn skip_extending(&self, pos: usize) -> usize {
+        let mut chars = self.text[pos..].char_indices().skip(1);
+        match chars.find(|&(_, c)| Grapheme::char_category(c) & EXTENDING == 0) {
+            Some((i, _)) => pos + i,
+            None => self.text.len(),
+        }
+    }
+
+    /// Returns the class of the character at `pos` and the offset just past it.
+    ///
+    /// Characters extending it are skipped, so the offset is that of the next character
+    /// that word boundary rules apply to.
+    #[inline]
+    fn class_at(&self, pos: usize) -> Option<(WordClass, usize)> {
+        let c = self.text[pos..].chars().next()?;
+        Some((WordClass::of(c), self.skip_extending(pos)))
+    }
+
+    /// Scans the segment starting at `start`.
+    ///
+    /// # Returns
+    ///
+    /// The byte offset just past the segment and whether it is a word.
+    fn scan_segment(&self, start: usize) -> (usize, bool) {
+        let Some((mut prev, mut end)) = self.class_at(start) else {
+            return (start, false);
+        };
+        let mut word = prev.is_word();
+
+        while let Some((next, next_end)) = self.class_at(end) {
+            if prev.joins(next) {
+                (prev, end) = (next, next_end);
+            } else if let Some((after, after_end)) = self.class_at(next_end) {
+                if !prev.joins_across(next, after) {
+                    break;
+                }
+                (prev, end) = (after, after_end);
+            } else {
+                break;
+            }
+            word |= prev.is_word();
+        }
+
+        (end, word)
+    }
+}
+
+impl<'a> Iterator for WordIterator<'a> {
+    type Item = Word<'a>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while self.pos < self.text.len() {
+            let start = self.pos;
+            let (end, word) = self.scan_segment(start);
+            self.pos = end;
+
+            if word {
+                return Some(Word {
+                    text: &self.text[start..end],
+                    offset: start,
+                });
+            }
+        }
+
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use heapless::Vec;
+
+    /// Collects the words of `input`
+    fn words(input: &str) -> Vec<&str, 16> {
+        WordIterator::new(input).map(|w| w.as_str()).collect()
+    }
+
+    #[test]
+    fn test_apostrophe() {
+        let text = "can't stop";
+        let spans: Vec<_, 4> = WordIterator::new(text)
+            .map(|w| (w.byte_offset(), w.byte_len()))
+            .collect();
+        assert_eq!(spans, [(0, 5), (6, 4)]);
+
+        assert_eq!(words("can\u{2019}t"), ["can\u{2019}t"]);
+        assert_eq!(words("'quoted'"), ["quoted"]);
+        assert_eq!(words("trailing' x"), ["trailing", "x"]);
+    }
+
+    #[test]
+    fn test_cjk_and_latin() {
+        assert_eq!(words("Hello世界world"), ["Hello", "世", "界", "world"]);
+        assert_eq!(words("日本語 text"), ["日", "本", "語", "text"]);
+        assert_eq!(words("カタカナとひら"), ["カタカナ", "と", "ひ", "ら"]);
+
+        let offsets: Vec<_, 4> = WordIterator::new("a世b").map(|w| w.byte_offset()).collect();
+        assert_eq!(offsets, [0, 1, 4]);
+    }
+
+    #[test]
+    fn test_numbers() {
+        assert_eq!(words("3.14 1,000,000"), ["3.14", "1,000,000"]);
+        assert_eq!(words("v2 2nd"), ["v2", "2nd"]);
+        assert_eq!(words("1.. 2,"), ["1", "2"]);
+        // Commas join numbers but not letters
+        assert_eq!(words("a,b"), ["a", "b"]);
+        assert_eq!(words("a:b 1:2"), ["a:b", "1", "2"]);
+    }
+
+    #[test]
+    fn test_connectors() {
+        assert_eq!(words("snake_case __init__"), ["snake_case", "__init__"]);
+        assert!(words("_ __").is_empty());
+    }
+
+    #[test]
+    fn test_separators() {
+        assert!(words("").is_empty());
+        assert!(words("  \t\r\n").is_empty());
+        assert_eq!(words("one, two; three!"), ["one", "two", "three"]);
+        assert_eq!(words("a-b"), ["a", "b"]);
+        assert_eq!(words("👋 hi 👋"), ["hi"]);
+    }
+
+    #[test]
+    fn test_extending() {
+        assert_eq!(words("cafe\u{0301} ok"), ["cafe\u{0301}", "ok"]);
+        assert_eq!(words("e\u{0301}'s"), ["e\u{0301}'s"]);
+        assert_eq!(words("नमस्ते दुनिया"), ["नमस्ते", "दुनिया"]);
+        // A combining mark alone isn't a word
+        assert_eq!(words("\u{0301} x"), ["x"]);
+    }
+}
@@ -0,0 +1,283 @@
+//! Writing grapheme clusters and ANSI escape sequences to a [`core::fmt::Write`] output.
+
+use core::fmt::Write;
+
+use crate::{GraphemeError, GraphemeIterator, Result, Token};
+
+/// Writes the grapheme clusters of `text` to `out`, one character at a time.
+///
+/// Clusters are written as [`GraphemeIterator`] yields them, without an intermediate string
+/// buffer, so text can be streamed straight into a display driver or serial port. ANSI escape
+/// sequences are passed through whole if `count_ansi` is set, and left out otherwise.
+/// Unterminated sequences are always left out, see [`coalesce_ansi`].
+///
+/// # Arguments
+///
+/// * `text` - The text to write
+/// * `count_ansi` - Whether to write ANSI escape sequences
+/// * `out` - The output to write to
+///
+/// # Returns
+///
+/// An error if `text` produces one, or `GraphemeError::WriteFailed` with the span of the
+/// cluster that `out` rejected. Clusters before the error have already been written.
+///
+/// # Examples
+///
+/// ```
+/// use graphmemes::{write_graphemes, Result};
+///
+/// # fn main() -> Result<()> {
+/// let mut out = String::new();
+/// write_graphemes("\x1b[1mhi 👋🏽\x1b[0m", false, &mut out)?;
+/// assert_eq!(out, "hi 👋🏽");
+/// # Ok(())
+/// # }
+/// ```
+pub fn write_graphemes<W: Write>(text: &str, count_ansi: bool, out: &mut W) -> Result<()> {
+    for token in GraphemeIterator::new(text, count_ansi).tokens() {
+        match token? {
+            Token::Grapheme(grapheme) => {
+                let written = grapheme
+                    .as_chars()
+                    .iter()
+                    .try_for_each(|&c| out.write_char(c));
+                written.map_err(|_| {
+                    GraphemeError::write_failed(grapheme.byte_offset(), grapheme.byte_len())
+                })?;
+            }
+            Token::Ansi { offset, len } => {
+                out.write_str(&text[offset..offset + len])
+                    .map_err(|_| GraphemeError::write_failed(offset, len))?;
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Writes `text` to `out` with runs of identical ANSI escape sequences collapsed.
+///
+/// When the same escape sequence repeats with no visible text in between, such as
+/// `\x1b[0m\x1b[0m`, only the first one is written. Sequences that differ from the one
+/// before them are written unchanged, as is all visible text.
+///
+/// Escape sequences left unterminated, cut short by another escape or the end of the text,
+/// are dropped rather than written, so a trailing fragment such as `\x1b[31` doesn't reach
+/// the output. Malformed sequences produce an error instead.
+///
+/// # Arguments
+///
+/// * `text` - The text to write
+/// * `out` - The output to write to
+///
+/// # Returns
+///
+/// An error if `text` produces one, or `GraphemeError::WriteFailed` with the span that `out`
+/// rejected. Text before the error has already been written.
+///
+/// # Examples
+///
+/// ```
+/// use graphmemes::{coalesce_ansi, Result};
+///
+/// # fn main() -> Result<()> {
+/// let mut out = String::new();
+/// coalesce_ansi("\x1b[0m\x1b[0mX\x1b[31m\x1b[1mY", &mut out)?;
+/// assert_eq!(out, "\x1b[0mX\x1b[31m\x1b[1mY");
+/// # Ok(())
+/// # }
+/// ```
+pub fn coalesce_ansi(text: &str, out: &mut impl Write) -> Result<()> {
+    // The escape sequence written last, as long as no visible text has followed it
+    let mut last_ansi = None;
+
+    for token in GraphemeIterator::new(text, true).tokens() {
+        let (offset, len) = match token? {
+            Token::Grapheme(grapheme) => {
+                last_ansi = None;
+                (grapheme.byte_offset(), grapheme.byte_len())
+            }
+            Token::Ansi { offset, len } => {
+                let sequence = &text[offset..offset + len];
+                if last_ansi == Some(sequence) {
+                    continue;
+                }
+                last_ansi = Some(sequence);
+                (offset, len)
+            }
+        };
+
+        out.write_str(&text[offset..offset + len])
+            .map_err(|_| GraphemeError::write_failed(offset, len))?;
+    }
+
+    Ok(())
+}
+
+/// Writes the grapheme clusters of `text` to `out` in reverse order.
+///
+/// Unlike reversing the characters, each cluster keeps its characters in order, so combining
+/// marks stay after their base and emoji sequences stay intact. Clusters are read from the
+/// back with [`DoubleEndedIterator::next_back`], so nothing is buffered. ANSI escape
+/// sequences are moved as whole units along with the clusters, while unterminated ones are
+/// dropped as in [`coalesce_ansi`].
+///
+/// # Arguments
+///
+/// * `text` - The text to reverse
+/// * `out` - The output to write to
+///
+/// # Returns
+///
+/// An error if `text` produces one, or `GraphemeError::WriteFailed` with the span that `out`
+/// rejected. Clusters before the error have already been written.
+///
+/// # Examples
+///
+/// ```
+/// use graphmemes::{reverse_graphemes, Result};
+///
+/// # fn main() -> Result<()> {
+/// let mut out = String::new();
+/// reverse_graphemes("abe\u{0301}", &mut out)?;
+/// assert_eq!(out, "e\u{0301}ba");
+/// # Ok(())
+/// # }
+/// ```
+pub fn reverse_graphemes(text: &str, out: &mut impl Write) -> Result<()> {
+    for token in GraphemeIterator::new(text, true).tokens().rev() {
+        let (offset, len) = match token? {
+            Token::Grapheme(grapheme) => (grapheme.byte_offset(), grapheme.byte_len()),
+            Token::Ansi { offset, len } => (offset, len),
+        };
+        out.write_str(&text[offset..offset + len])
+            .map_err(|_| GraphemeError::write_failed(offset, len))?;
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use heapless::String;
+
+    #[test]
+    fn test_round_trip() {
+        for text in ["hello", "a👋🏽b🇺🇸c", "e\u{0301}\r\n", "👨\u{200D}👩\u{200D}👧", ""] {
+            let mut out = String::<64>::new();
+            write_graphemes(text, false, &mut out).unwrap();
+            assert_eq!(out, text);
+        }
+    }
+
+    #[test]
+    fn test_ansi() {
+        let text = "\x1b[31mred\x1b[0m text";
+
+        let mut out = String::<32>::new();
+        write_graphemes(text, true, &mut out).unwrap();
+        assert_eq!(out, text);
+
+        let mut out = String::<32>::new();
+        write_graphemes(text, false, &mut out).unwrap();
+        assert_eq!(out, "red text");
+
+        // A trailing unterminated sequence is dropped
+        let mut out = String::<32>::new();
+        write_graphemes("X\x1b[31", true, &mut out).unwrap();
+        assert_eq!(out, "X");
+    }
+
+    #[test]
+    fn test_errors() {
+        let mut out = String::<32>::new();
+        let result = write_graphemes("ab\x1b\u{1234}c", true, &mut out);
+        assert!(matches!(result, Err(GraphemeError::InvalidAnsiSequence { .. })));
+        assert_eq!(out, "ab");
+
+        // The cluster that doesn't fit is reported and nothing after it is written
+        let mut out = String::<6>::new();
+        let result = write_graphemes("ab👋🏽c", false, &mut out);
+        assert_eq!(result, Err(GraphemeError::write_failed(2, 8)));
+        assert_eq!(out.len(), 6);
+    }
+
+    #[test]
+    fn test_coalesce_ansi() {
+        let mut out = String::<32>::new();
+        coalesce_ansi("\x1b[0m\x1b[0mX\x1b[31m\x1b[31mY", &mut out).unwrap();
+        assert_eq!(out, "\x1b[0mX\x1b[31mY");
+
+        let mut out = String::<64>::new();
+        coalesce_ansi("\x1b[0m\x1b[0m\x1b[0m\x1b[0m", &mut out).unwrap();
+        assert_eq!(out, "\x1b[0m");
+    }
+
+    #[test]
+    fn test_coalesce_keeps_distinct_sequences() {
+        // Repeats separated by text or by a different sequence are kept
+        for text in [
+            "\x1b[31mA\x1b[31mB",
+            "\x1b[31m\x1b[0m\x1b[31m",
+            "\x1b[1m\x1b[31m",
+            "plain 👋🏽 text",
+            "",
+        ] {
+            let mut out = String::<32>::new();
+            coalesce_ansi(text, &mut out).unwrap();
+            assert_eq!(out, text);
+        }
+    }
+
+    #[test]
+    fn test_coalesce_errors() {
+        let mut out = String::<32>::new();
+        let result = coalesce_ansi("ab\x1b\u{1234}c", &mut out);
+        assert!(matches!(result, Err(GraphemeError::InvalidAnsiSequence { .. })));
+
+        let mut out = String::<3>::new();
+        let result = coalesce_ansi("\x1b[0m\x1b[0m", &mut out);
+        assert_eq!(result, Err(GraphemeError::write_failed(0, 4)));
+    }
+
+    #[test]
+    fn test_coalesce_unterminated() {
+        // Unterminated sequences are dropped, wherever they're cut short
+        let mut out = String::<32>::new();
+        coalesce_ansi("X\x1b[31", &mut out).unwrap();
+        assert_eq!(out, "X");
+
+        let mut out = String::<32>::new();
+        coalesce_ansi("X\x1b[31\x1b[0mY", &mut out).unwrap();
+        assert_eq!(out, "X\x1b[0mY");
+    }
+
+    #[test]
+    fn test_reverse_graphemes() {
+        let mut out = String::<64>::new();
+        reverse_graphemes("ab e\u{0301}👨\u{200D}👩\u{200D}👧🇺🇸\r\n", &mut out).unwrap();
+        assert_eq!(out, "\r\n🇺🇸👨\u{200D}👩\u{200D}👧e\u{0301} ba");
+
+        let mut out = String::<8>::new();
+        reverse_graphemes("", &mut out).unwrap();
+        assert_eq!(out, "");
+    }
+
+    #[test]
+    fn test_reverse_graphemes_ansi() {
+        let mut out = String::<32>::new();
+        reverse_graphemes("\x1b[31mab\x1b[0m", &mut out).unwrap();
+        assert_eq!(out, "\x1b[0mba\x1b[31m");
+
+        let mut out = String::<32>::new();
+        reverse_graphemes("X\x1b[31\x1b[0mY", &mut out).unwrap();
+        assert_eq!(out, "Y\x1b[0mX");
+
+        let mut out = String::<4>::new();
+        let result = reverse_graphemes("ab👋", &mut out);
+        assert_eq!(result, Err(GraphemeError::write_failed(1, 1)));
+        assert_eq!(out, "👋");
+    }
+}
@@ -6,4 +6,7 @@
 // Need to redo the implementation I have of this elsewhere
 // Just a stub for now
 
+#[macro_use]
+mod macros;
+
 pub mod gpio;
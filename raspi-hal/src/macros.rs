@@ -0,0 +1,67 @@
+//! Internal macros for describing the platform's pins
+
+/// Declares the supported [`PinMode`](embedded_hal_ext::digital::PinMode)s of each pin.
+///
+/// Generates a `const fn capabilities(self) -> &'static [PinMode]` on the given pin type, meant to back
+/// [`Configurable::capabilities`](embedded_hal_ext::digital::Configurable::capabilities).
+/// Modes are written as bare `PinMode` variants, and pins that aren't listed default to `[Input, Output]`.
+///
+/// ```ignore
+/// pin_capabilities! {
+///     Gpio {
+///         SDA => [Input, Output, Alt(0)],
+///         GPCLK0 => [Input, Output, Alt(0), Alt(5)],
+///     }
+/// }
+/// ```
+// Unused until the gpio module grows its pin types
+#[allow(unused_macros)]
+macro_rules! pin_capabilities {
+    ($pin:ty { $($name:ident => [$($mode:expr),* $(,)?]),* $(,)? }) => {
+        impl $pin {
+            /// Returns the modes supported by this pin
+            #[allow(unreachable_patterns)]
+            pub const fn capabilities(self) -> &'static [::embedded_hal_ext::digital::PinMode] {
+                #[allow(unused_imports)]
+                use ::embedded_hal_ext::digital::PinMode::*;
+                match self {
+                    $(<$pin>::$name => &[$($mode),*],)*
+                    _ => &[Input, Output],
+                }
+            }
+        }
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use embedded_hal_ext::digital::PinMode;
+
+    #[allow(non_camel_case_types, clippy::upper_case_acronyms)]
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    enum Gpio {
+        SDA,
+        GPCLK0,
+        GPIO17,
+    }
+
+    pin_capabilities! {
+        Gpio {
+            SDA => [Input, Output, Alt(0)],
+            GPCLK0 => [Input, Output, Alt(0), Alt(5)],
+        }
+    }
+
+    #[test]
+    fn test_pin_capabilities() {
+        assert_eq!(Gpio::SDA.capabilities(), &[PinMode::Input, PinMode::Output, PinMode::Alt(0)]);
+
+        let gpclk = Gpio::GPCLK0.capabilities();
+        assert!(gpclk.contains(&PinMode::Alt(0)));
+        assert!(gpclk.contains(&PinMode::Alt(5)));
+
+        let plain = Gpio::GPIO17.capabilities();
+        assert_eq!(plain, &[PinMode::Input, PinMode::Output]);
+        assert!(!plain.iter().any(|mode| matches!(mode, PinMode::Alt(_))));
+    }
+}